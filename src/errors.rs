@@ -15,6 +15,7 @@ pub enum Error {
     RuntimeError(String),
     DriverError(String),
     JsonParseError(json::ParserError),
+    JsonDecodeError(json::DecoderError),
     IoError(io::IoError)
 }
 
@@ -44,23 +45,28 @@ impl FromError<json::ParserError> for Error {
     fn from_error(e: json::ParserError) -> Error { Error::JsonParseError(e) }
 }
 
+impl FromError<json::DecoderError> for Error {
+    fn from_error(e: json::DecoderError) -> Error { Error::JsonDecodeError(e) }
+}
+
 impl ::std::error::Error for Error {
     fn description(&self) -> &str {
         use Error::{ClientError, CompileError, RuntimeError, DriverError};
-        use Error::{JsonParseError, IoError};
+        use Error::{JsonParseError, JsonDecodeError, IoError};
         match *self {
             ClientError(..) => "RethinkDB client error",
             CompileError(..) => "RethinkDB compile error",
             RuntimeError(..) => "RethinkDB runtime error",
             DriverError(..) => "RethinkDB driver error",
             JsonParseError(..) => "RethinkDB JSON error",
+            JsonDecodeError(..) => "RethinkDB JSON decoding error",
             IoError(ref io_err) => io_err.description()
         }
     }
 
     fn detail(&self) -> Option<String> {
         use Error::{ClientError, CompileError, RuntimeError, DriverError};
-        use Error::{JsonParseError, IoError};
+        use Error::{JsonParseError, JsonDecodeError, IoError};
         match *self {
             ClientError(ref s) => Some(s.clone()),
             CompileError(ref s) => Some(s.clone()),
@@ -69,6 +75,9 @@ impl ::std::error::Error for Error {
             JsonParseError(ref err) => {
                 Some(format!("{}", err))
             },
+            JsonDecodeError(ref err) => {
+                Some(format!("{}", err))
+            },
             IoError(ref io_err) => io_err.detail()
         }
     }
@@ -1,12 +1,37 @@
 use errors::RdbResult;
 use net::{Connection, Response, ResponseKind};
 use query;
-use serialize::json;
+use serialize::Decodable;
+use serialize::json::{mod, Json};
 
 pub trait FromResponse<'a> {
     fn from_response(Response, &'a mut Connection) -> RdbResult<Self>;
 }
 
+/// Unwraps the single datum of a `SUCCESS_ATOM` response.
+pub fn atom(res: Response) -> RdbResult<Json> {
+    use errors::Error::DriverError;
+
+    if res.kind != ResponseKind::Atom {
+        return Err(DriverError(format!("expected an atom, got {}", res)));
+    }
+    match res.values {
+        json::Array(mut values) => {
+            if values.len() != 1 {
+                return Err(DriverError(format!("expected one datum, got {}", values.len())));
+            }
+            Ok(values.pop().unwrap())
+        }
+        values => Err(DriverError(format!("couldn't find datum in {}", values)))
+    }
+}
+
+/// Decodes a datum into a Rust value.
+pub fn decode<T: Decodable<json::Decoder, json::DecoderError>>(datum: Json) -> RdbResult<T> {
+    let mut decoder = json::Decoder::new(datum);
+    Ok(try!(Decodable::decode(&mut decoder)))
+}
+
 impl<'a> FromResponse<'a> for Vec<String> {
     fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Vec<String>> {
         use errors::Error::DriverError;
@@ -37,10 +62,67 @@ impl<'a> FromResponse<'a> for json::Json {
     }
 }
 
+impl<'a> FromResponse<'a> for f64 {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<f64> {
+        use errors::Error::DriverError;
+
+        let value = try!(atom(res));
+        match value.as_f64() {
+            Some(n) => Ok(n),
+            None => Err(DriverError(format!("expected a number, got {}", value)))
+        }
+    }
+}
+
+impl<'a> FromResponse<'a> for u64 {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<u64> {
+        use errors::Error::DriverError;
+
+        let value = try!(atom(res));
+        match value.as_u64() {
+            Some(n) => Ok(n),
+            None => Err(DriverError(format!("expected an unsigned integer, got {}", value)))
+        }
+    }
+}
+
+impl<'a> FromResponse<'a> for i64 {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<i64> {
+        use errors::Error::DriverError;
+
+        let value = try!(atom(res));
+        match value.as_i64() {
+            Some(n) => Ok(n),
+            None => Err(DriverError(format!("expected an integer, got {}", value)))
+        }
+    }
+}
+
+impl<'a> FromResponse<'a> for bool {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<bool> {
+        use errors::Error::DriverError;
+
+        match try!(atom(res)) {
+            json::Boolean(b) => Ok(b),
+            value => Err(DriverError(format!("expected a bool, got {}", value)))
+        }
+    }
+}
+
+impl<'a> FromResponse<'a> for String {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<String> {
+        use errors::Error::DriverError;
+
+        match try!(atom(res)) {
+            json::String(s) => Ok(s),
+            value => Err(DriverError(format!("expected a string, got {}", value)))
+        }
+    }
+}
+
 impl<'a> FromResponse<'a> for query::Writes {
     // vvvv this is all very very bad
     fn from_response(res: Response, _: &mut Connection) -> RdbResult<query::Writes> {
-        use serialize::Decodable;
         let list = res.values.as_array().unwrap();
         let mut decoder = json::Decoder::new(list[0].clone()); // FIXME
         let insertion: query::Writes = Decodable::decode(&mut decoder).unwrap(); // FIXME
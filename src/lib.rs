@@ -1,4 +1,4 @@
-#![feature(associated_types, if_let, macro_rules, phase)]
+#![feature(associated_types, if_let, macro_rules, phase, unsafe_destructor)]
 #![experimental]
 
 #[phase(plugin, link)] extern crate log;
@@ -6,12 +6,14 @@ extern crate serialize;
 
 pub use errors::{Error, RdbResult};
 pub use net::{connect, Connection};
-pub use query::Query;
+pub use query::{Expr, Query};
 
 mod errors;
 mod from_response;
+pub mod migrations;
 mod net;
 pub mod query;
+pub mod schema;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,554 @@
+//! Versioned schema and data migrations, tracked in a `_migrations` table.
+//!
+//! ```
+//! let migrator = Migrator::new("app")
+//!     .migration(Migration::new(1, "index users by name")
+//!         .up(r::table("users").index_create("name"))
+//!         .down(r::table("users").index_drop("name")))
+//!     .migration(Migration::new(2, "rename the name index")
+//!         .up(r::table("users").index_rename("name", "full_name"))
+//!         .down(r::table("users").index_rename("full_name", "name")))
+//!     // Nothing to undo here, so no down queries
+//!     .migration(Migration::new(3, "give every user a role")
+//!         .up(r::table("users").update(json!({"role": "member"}))));
+//!
+//! // See what would run first
+//! for planned in try!(migrator.plan(&mut conn)).iter() {
+//!     println!("{}", planned);
+//! }
+//! match migrator.migrate(&mut conn) {
+//!     Ok(applied) => println!("applied {}", applied),
+//!     Err(e) => panic!("applied {} before failing: {}", e.done, e.error)
+//! }
+//! ```
+
+use std::fmt;
+use std::error::FromError;
+
+use serialize::json::{Json, ToJson};
+
+use errors::{Error, RdbResult};
+use errors::Error::DriverError;
+use from_response;
+use net::{mod, Connection, ResponseKind};
+use query as r;
+use query::{Expr, Query, Term};
+use query::term_type;
+use schema::{Schema, DbSchema, TableSchema};
+
+const MIGRATIONS_TABLE: &'static str = "_migrations";
+// The id of the document whose existence means a process is migrating. The
+// applied migrations are recorded by version number, so it can't clash.
+const LOCK_ID: &'static str = "lock";
+
+/// A migration: the queries which make a change, and those which undo it.
+///
+/// A migration isn't atomic. If one of its queries fails, the queries before
+/// it stay applied, and the migration isn't recorded as applied (or rolled
+/// back); it has to be cleaned up by hand before migrating again. Queries
+/// which are safe to run twice make that easier.
+pub struct Migration {
+    version: u64,
+    name: String,
+    up: Vec<Json>,
+    down: Vec<Json>
+}
+
+impl Migration {
+    /// Migrations are applied in order of `version`, which must be unique.
+    pub fn new(version: u64, name: &str) -> Migration {
+        Migration { version: version, name: name.into_string(), up: vec![], down: vec![] }
+    }
+
+    /// Adds a query to run when applying the migration.
+    pub fn up<T: Term + ToJson>(mut self, query: T) -> Migration {
+        self.up.push(query.to_json());
+        self
+    }
+
+    /// Adds a query to run when rolling the migration back. These run in the
+    /// reverse of the order they're added in.
+    pub fn down<T: Term + ToJson>(mut self, query: T) -> Migration {
+        self.down.push(query.to_json());
+        self
+    }
+}
+
+/// Applies and rolls back the migrations of a database.
+///
+/// Only one process at a time may migrate a database: while one is, the
+/// `_migrations` table holds a lock document, and others fail to start. If a
+/// process dies while migrating, the lock stays behind until it's cleared
+/// with `force_unlock`.
+pub struct Migrator {
+    db: String,
+    migrations: Vec<Migration>
+}
+
+#[deriving(Clone, Show, PartialEq)]
+pub enum Direction {
+    Up,
+    Down
+}
+
+/// The queries a migration would run, from `Migrator::plan` or
+/// `Migrator::plan_rollback`. Printing it shows the migration, then the term
+/// type of each query, e.g. `up 1: index users by name` followed by
+/// `index_create` and the `insert` recording it.
+#[deriving(Clone)]
+pub struct Planned {
+    pub version: u64,
+    pub name: String,
+    pub direction: Direction,
+    pub queries: Vec<Json>
+}
+
+impl fmt::Show for Planned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = if self.direction == Direction::Up { "up" } else { "down" };
+        try!(writeln!(f, "{} {}: {}", direction, self.version, self.name));
+        for query in self.queries.iter() {
+            let term_type = query.as_array().and_then(|q| q.head()).and_then(|t| t.as_i64());
+            match term_type.and_then(|t| term_type::name(t)) {
+                Some(name) => try!(writeln!(f, "    {}", name)),
+                None => try!(writeln!(f, "    {}", query))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The error from `Migrator::migrate` or `Migrator::rollback`, along with
+/// the versions of the migrations which were applied (or rolled back) before
+/// it happened.
+#[deriving(Show)]
+pub struct MigrationError {
+    pub done: Vec<u64>,
+    pub error: Error
+}
+
+impl FromError<Error> for MigrationError {
+    fn from_error(e: Error) -> MigrationError {
+        MigrationError { done: vec![], error: e }
+    }
+}
+
+// Lets `try!` use a migration's result in a function returning `RdbResult`;
+// the versions which were done are dropped.
+impl FromError<MigrationError> for Error {
+    fn from_error(e: MigrationError) -> Error {
+        e.error
+    }
+}
+
+impl ::std::error::Error for MigrationError {
+    fn description(&self) -> &str {
+        "RethinkDB migration error"
+    }
+
+    fn detail(&self) -> Option<String> {
+        Some(format!("failed after migrating {}: {}", self.done, self.error))
+    }
+
+    fn cause(&self) -> Option<&::std::error::Error> {
+        Some(&self.error as &::std::error::Error)
+    }
+}
+
+impl Migrator {
+    pub fn new(db: &str) -> Migrator {
+        Migrator { db: db.into_string(), migrations: vec![] }
+    }
+
+    pub fn migration(mut self, migration: Migration) -> Migrator {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Applies every migration which hasn't been yet, in order, returning
+    /// their versions. Queries run with the migrated database as their
+    /// default, so they can use `r::table` directly; the connection's own
+    /// default is left alone.
+    pub fn migrate(&self, conn: &mut Connection) -> Result<Vec<u64>, MigrationError> {
+        let migrations = try!(self.sorted());
+        let applied = try!(self.start(conn));
+        self.finish(conn, pending(migrations, applied.as_slice()), &Direction::Up)
+    }
+
+    /// Rolls back every applied migration with a version greater than
+    /// `version`, newest first, returning their versions. Pass 0 to roll
+    /// back everything.
+    pub fn rollback(&self, conn: &mut Connection, version: u64) -> Result<Vec<u64>, MigrationError> {
+        let migrations = try!(self.sorted());
+        let applied = try!(self.start(conn));
+        self.finish(conn, rolled_back(migrations, applied.as_slice(), version), &Direction::Down)
+    }
+
+    /// The queries `migrate` would run, without running them or taking the
+    /// lock.
+    pub fn plan(&self, conn: &mut Connection) -> RdbResult<Vec<Planned>> {
+        let migrations = try!(self.sorted());
+        let applied = try!(self.recorded(conn));
+        Ok(pending(migrations, applied.as_slice()).into_iter().map(|m| {
+            self.planned(m, &Direction::Up)
+        }).collect())
+    }
+
+    /// The queries `rollback` would run, without running them or taking the
+    /// lock.
+    pub fn plan_rollback(&self, conn: &mut Connection, version: u64) -> RdbResult<Vec<Planned>> {
+        let migrations = try!(self.sorted());
+        let applied = try!(self.recorded(conn));
+        Ok(rolled_back(migrations, applied.as_slice(), version).into_iter().map(|m| {
+            self.planned(m, &Direction::Down)
+        }).collect())
+    }
+
+    /// Deletes the lock left behind by a process which died while migrating.
+    /// Make sure no other process is migrating first.
+    pub fn force_unlock(&self, conn: &mut Connection) -> RdbResult<()> {
+        self.unlock(conn)
+    }
+
+    // Creates the migrations table if need be, and takes the lock, returning
+    // the versions of the applied migrations.
+    fn start(&self, conn: &mut Connection) -> RdbResult<Vec<u64>> {
+        let schema = Schema::new()
+            .db(DbSchema::new(self.db.as_slice()).table(TableSchema::new(MIGRATIONS_TABLE)));
+        try!(schema.ensure(conn));
+        try!(self.lock(conn));
+        match self.applied(conn) {
+            Ok(applied) => Ok(applied),
+            Err(e) => {
+                let _ = self.unlock(conn);
+                Err(e)
+            }
+        }
+    }
+
+    // Runs the migrations and releases the lock, even if one fails.
+    fn finish(&self, conn: &mut Connection, migrations: Vec<&Migration>,
+              direction: &Direction) -> Result<Vec<u64>, MigrationError> {
+        let mut done = vec![];
+        let mut result = Ok(());
+        for migration in migrations.into_iter() {
+            result = self.run(conn, migration, direction);
+            if result.is_err() {
+                break;
+            }
+            done.push(migration.version);
+        }
+
+        let unlocked = self.unlock(conn);
+        match result.and(unlocked) {
+            Ok(()) => Ok(done),
+            Err(e) => Err(MigrationError { done: done, error: e })
+        }
+    }
+
+    fn run(&self, conn: &mut Connection, migration: &Migration, direction: &Direction) -> RdbResult<()> {
+        for step in self.steps(migration, direction).iter() {
+            try!(run_step(conn, step, self.db.as_slice()));
+        }
+        Ok(())
+    }
+
+    fn planned(&self, migration: &Migration, direction: &Direction) -> Planned {
+        Planned {
+            version: migration.version,
+            name: migration.name.clone(),
+            direction: direction.clone(),
+            queries: self.steps(migration, direction)
+        }
+    }
+
+    // The queries of a migration, followed by the one recording that it was
+    // applied or rolled back.
+    fn steps(&self, migration: &Migration, direction: &Direction) -> Vec<Json> {
+        let (mut steps, record) = match *direction {
+            Direction::Up => {
+                let record = r::make_obj()
+                    .field("id", migration.version)
+                    .field("name", migration.name.clone())
+                    .field("applied_at", r::now());
+                (migration.up.clone(), self.table().insert(record).to_json())
+            }
+            Direction::Down => {
+                let mut steps = migration.down.clone();
+                steps.reverse();
+                (steps, self.table().get(migration.version).delete().to_json())
+            }
+        };
+        steps.push(record);
+        steps
+    }
+
+    fn sorted(&self) -> RdbResult<Vec<&Migration>> {
+        let mut migrations: Vec<&Migration> = self.migrations.iter().collect();
+        migrations.sort_by(|a, b| a.version.cmp(&b.version));
+        for pair in migrations.windows(2) {
+            if pair[0].version == pair[1].version {
+                return Err(DriverError(format!("duplicate migration version {}", pair[0].version)));
+            }
+        }
+        Ok(migrations)
+    }
+
+    fn table(&self) -> r::Table {
+        r::db(self.db.as_slice()).table(MIGRATIONS_TABLE)
+    }
+
+    fn table_exists(&self, conn: &mut Connection) -> RdbResult<bool> {
+        let dbs = try!(r::db_list().run(conn));
+        if !dbs.iter().any(|x| x.as_slice() == self.db.as_slice()) {
+            return Ok(false);
+        }
+        let tables = try!(r::db(self.db.as_slice()).table_list().run(conn));
+        Ok(tables.iter().any(|x| x.as_slice() == MIGRATIONS_TABLE))
+    }
+
+    // The versions of the applied migrations, without creating the table if
+    // it doesn't exist yet.
+    fn recorded(&self, conn: &mut Connection) -> RdbResult<Vec<u64>> {
+        if try!(self.table_exists(conn)) { self.applied(conn) } else { Ok(vec![]) }
+    }
+
+    fn applied(&self, conn: &mut Connection) -> RdbResult<Vec<u64>> {
+        let ids: Json = try!(self.table().get_field("id").coerce_to("array").run(conn));
+        let ids = match ids.as_array().and_then(|x| x.head()).and_then(|x| x.as_array()) {
+            Some(ids) => ids.clone(),
+            None => return Err(DriverError(format!("couldn't read applied migrations from {}", ids)))
+        };
+        // The lock's id isn't a number, so it's skipped
+        Ok(ids.iter().filter_map(|id| id.as_u64()).collect())
+    }
+
+    fn lock(&self, conn: &mut Connection) -> RdbResult<()> {
+        let lock = r::make_obj().field("id", LOCK_ID).field("locked_at", r::now());
+        let writes = try!(self.table().insert(lock).run(conn));
+        if writes.inserted == Some(1) {
+            Ok(())
+        } else {
+            Err(DriverError(format!("migrations of `{}` are locked by another process", self.db)))
+        }
+    }
+
+    fn unlock(&self, conn: &mut Connection) -> RdbResult<()> {
+        try!(self.table().get(LOCK_ID).delete().run(conn));
+        Ok(())
+    }
+}
+
+// The migrations which haven't been applied, oldest first.
+fn pending<'a>(migrations: Vec<&'a Migration>, applied: &[u64]) -> Vec<&'a Migration> {
+    migrations.into_iter().filter(|m| !applied.contains(&m.version)).collect()
+}
+
+// The applied migrations newer than `version`, newest first.
+fn rolled_back<'a>(migrations: Vec<&'a Migration>, applied: &[u64], version: u64) -> Vec<&'a Migration> {
+    let mut migrations: Vec<&Migration> = migrations.into_iter().filter(|m| {
+        m.version > version && applied.contains(&m.version)
+    }).collect();
+    migrations.reverse();
+    migrations
+}
+
+// Runs a query for its effects, in the migrated database. Steps are expected
+// to run to a single result, such as a write summary; one which returns a
+// sequence fails, and if the server is holding a cursor open for the rest of
+// it, that's stopped first.
+fn run_step(conn: &mut Connection, step: &Json, db: &str) -> RdbResult<()> {
+    let res = try!(net::run_in_db(conn, step.clone(), db));
+    match res.kind {
+        ResponseKind::Partial | ResponseKind::Feed => { try!(net::stop_query(conn, res.token)); }
+        _ => { }
+    }
+    check_writes(&try!(from_response::atom(res)))
+}
+
+// Write queries report failed documents in their result, rather than failing
+// the query, so treat any of those as a failure of the step.
+fn check_writes(datum: &Json) -> RdbResult<()> {
+    if datum.find("errors").and_then(|n| n.as_u64()).unwrap_or(0) > 0 {
+        let error = datum.find("first_error").and_then(|e| e.as_string()).unwrap_or("unknown error");
+        return Err(DriverError(format!("migration query failed: {}", error)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Listener, Acceptor};
+    use std::io::net::tcp::{TcpListener, TcpStream};
+    use std::task;
+
+    use serialize::json::ToJson;
+
+    use errors::{Error, RdbResult};
+    use net;
+    use query as r;
+
+    use super::{Migration, MigrationError, Migrator, Direction};
+    use super::{check_writes, pending, rolled_back, run_step};
+
+    #[test]
+    fn test_sorted() {
+        let migrator = Migrator::new("app")
+            .migration(Migration::new(2, "b").up(r::table("foo").index_create("b")))
+            .migration(Migration::new(1, "a").up(r::table("foo").index_create("a")));
+        let versions: Vec<u64> = migrator.sorted().unwrap().iter().map(|m| m.version).collect();
+        assert_eq!(versions, vec![1, 2]);
+
+        let migrator = migrator.migration(Migration::new(1, "c"));
+        assert!(migrator.sorted().is_err());
+    }
+
+    #[test]
+    fn test_selection() {
+        let migrator = Migrator::new("app")
+            .migration(Migration::new(1, "a"))
+            .migration(Migration::new(2, "b"))
+            .migration(Migration::new(3, "c"));
+        let versions = |migrations: Vec<&Migration>| -> Vec<u64> {
+            migrations.iter().map(|m| m.version).collect()
+        };
+
+        assert_eq!(versions(pending(migrator.sorted().unwrap(), &[2])), vec![1, 3]);
+        assert_eq!(versions(rolled_back(migrator.sorted().unwrap(), &[1, 2, 3], 1)), vec![3, 2]);
+        assert_eq!(versions(rolled_back(migrator.sorted().unwrap(), &[1, 3], 0)), vec![3, 1]);
+    }
+
+    #[test]
+    fn test_steps() {
+        let foo = || r::table("foo");
+        let migration = Migration::new(1, "a")
+            .up(foo().index_create("a"))
+            .up(foo().index_create("b"))
+            .down(foo().index_drop("a"))
+            .down(foo().index_drop("b"));
+        let migrator = Migrator::new("app");
+
+        let planned = migrator.planned(&migration, &Direction::Up);
+        assert_eq!(planned.queries, vec![
+            foo().index_create("a").to_json(),
+            foo().index_create("b").to_json(),
+            json!([56, [[15, [[14, ["app"]], "_migrations"]],
+                        [3, [], {"applied_at": [103, []], "id": 1, "name": "a"}]]])
+        ]);
+
+        // Down steps undo the up steps, so they run in reverse
+        let planned = migrator.planned(&migration, &Direction::Down);
+        assert_eq!(planned.queries, vec![
+            foo().index_drop("b").to_json(),
+            foo().index_drop("a").to_json(),
+            json!([54, [[16, [[15, [[14, ["app"]], "_migrations"]], 1]]]])
+        ]);
+    }
+
+    #[test]
+    fn test_planned_show() {
+        let migration = Migration::new(1, "index users by name")
+            .up(r::table("users").index_create("name"))
+            .down(r::table("users").index_drop("name"));
+        let migrator = Migrator::new("app");
+
+        assert_eq!(format!("{}", migrator.planned(&migration, &Direction::Up)),
+                   "up 1: index users by name\n    index_create\n    insert\n".to_string());
+        assert_eq!(format!("{}", migrator.planned(&migration, &Direction::Down)),
+                   "down 1: index users by name\n    index_drop\n    delete\n".to_string());
+    }
+
+    #[test]
+    fn test_check_writes() {
+        assert!(check_writes(&json!({"inserted": 1, "errors": 0})).is_ok());
+        assert!(check_writes(&json!({"created": 1})).is_ok());
+        match check_writes(&json!({"errors": 1, "first_error": "Duplicate primary key `id`"})) {
+            Err(Error::DriverError(ref msg)) => assert!(msg.as_slice().contains("Duplicate primary key")),
+            res => panic!("expected the write error, got {}", res)
+        }
+    }
+
+    #[test]
+    fn test_migration_error() {
+        use std::error::Error as StdError;
+
+        fn migrate() -> RdbResult<Vec<u64>> {
+            let res: Result<Vec<u64>, MigrationError> =
+                Err(MigrationError { done: vec![1], error: Error::DriverError("oops".to_string()) });
+            Ok(try!(res))
+        }
+
+        match migrate() {
+            Err(Error::DriverError(ref msg)) => assert_eq!(msg.as_slice(), "oops"),
+            res => panic!("expected the migration's error, got {}", res)
+        }
+
+        let err = MigrationError { done: vec![1, 2], error: Error::DriverError("oops".to_string()) };
+        assert_eq!(err.detail(), Some("failed after migrating [1, 2]: DriverError(oops)".to_string()));
+        assert_eq!(err.cause().unwrap().description(), "RethinkDB driver error");
+    }
+
+    #[test]
+    fn test_duplicate_versions() {
+        // A server which accepts the handshake, then reports everything else
+        // the driver sends
+        let mut listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.socket_name().unwrap().port;
+        let mut acceptor = listener.listen().unwrap();
+        let (sender, receiver) = channel();
+        task::spawn(proc() {
+            let mut stream = acceptor.accept().unwrap();
+            stream.read_exact(12).unwrap();
+            stream.write(b"SUCCESS\0").unwrap();
+            sender.send(stream.read_to_end().unwrap());
+        });
+
+        let migrator = Migrator::new("app")
+            .migration(Migration::new(1, "a"))
+            .migration(Migration::new(1, "b"));
+        {
+            let mut conn = net::connect("127.0.0.1", port).unwrap();
+            let err = migrator.migrate(&mut conn).unwrap_err();
+            assert!(err.done.is_empty());
+            assert!(migrator.rollback(&mut conn, 0).is_err());
+            assert!(migrator.plan(&mut conn).is_err());
+        }
+        // Neither the lock nor anything else was queried
+        assert!(receiver.recv().is_empty());
+    }
+
+    #[test]
+    fn test_run_step_sequence() {
+        fn read_query(stream: &mut TcpStream) -> String {
+            stream.read_le_u64().unwrap(); // the token
+            let len = stream.read_le_i32().unwrap();
+            String::from_utf8(stream.read_exact(len as uint).unwrap()).unwrap()
+        }
+
+        fn reply(stream: &mut TcpStream, response: &str) {
+            stream.write_le_u64(0).unwrap();
+            stream.write_le_u32(response.len() as u32).unwrap();
+            stream.write(response.as_bytes()).unwrap();
+        }
+
+        // A server which answers the step with the first batch of a sequence,
+        // then reports the query that follows it
+        let mut listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.socket_name().unwrap().port;
+        let mut acceptor = listener.listen().unwrap();
+        let (sender, receiver) = channel();
+        task::spawn(proc() {
+            let mut stream = acceptor.accept().unwrap();
+            stream.read_exact(12).unwrap();
+            stream.write(b"SUCCESS\0").unwrap();
+            read_query(&mut stream);
+            reply(&mut stream, r#"{"t": 3, "r": [{"id": 1}]}"#);
+            sender.send(read_query(&mut stream));
+            reply(&mut stream, r#"{"t": 2, "r": []}"#);
+        });
+
+        let mut conn = net::connect("127.0.0.1", port).unwrap();
+        assert!(run_step(&mut conn, &r::table("foo").to_json(), "app").is_err());
+        assert_eq!(receiver.recv(), "[3]".to_string());
+    }
+}
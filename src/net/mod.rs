@@ -1,5 +1,6 @@
 use errors::RdbResult;
 use errors::Error::DriverError;
+use query::{BinaryFormat, TimeFormat};
 pub use self::response::{Response, ResponseKind};
 
 use std::collections::TreeMap;
@@ -23,7 +24,9 @@ pub struct Connection {
 }
 
 struct OptArgs {
-    db: Option<String>
+    db: Option<String>,
+    time_format: Option<TimeFormat>,
+    binary_format: Option<BinaryFormat>
 }
 
 impl<'a> ToJson for &'a OptArgs {
@@ -34,6 +37,12 @@ impl<'a> ToJson for &'a OptArgs {
         if let Some(ref s) = self.db {
             d.insert("db".to_string(), r::db(s.as_slice()).to_json());
         }
+        if let Some(ref f) = self.time_format {
+            d.insert("time_format".to_string(), f.to_json());
+        }
+        if let Some(ref f) = self.binary_format {
+            d.insert("binary_format".to_string(), f.to_json());
+        }
         json::Object(d)
     }
 }
@@ -48,19 +57,68 @@ pub fn run(conn: &mut Connection, term: Json) -> RdbResult<Response> {
     conn.run(term)
 }
 
+/// Runs a query with `db` as its default database, leaving the connection's
+/// own default as it is.
+pub fn run_in_db(conn: &mut Connection, term: Json, db: &str) -> RdbResult<Response> {
+    let opt_args = OptArgs {
+        db: Some(db.into_string()),
+        time_format: conn.opt_args.time_format.clone(),
+        binary_format: conn.opt_args.binary_format.clone()
+    };
+    conn.start(term, (&opt_args).to_json())
+}
+
+/// A handle on the connection's socket, which can be used to shut it down
+/// from another task, e.g. to interrupt a changefeed waiting for changes.
+pub fn socket(conn: &Connection) -> TcpStream {
+    conn.stream.get_ref().clone()
+}
+
+/// Asks for the next batch of results of a partial or feed response.
+pub fn continue_query(conn: &mut Connection, token: u64) -> RdbResult<Response> {
+    const CONTINUE: u8 = 2;
+    conn.send(token, json::Array(vec![CONTINUE.to_json()]))
+}
+
+/// Stops a partial or feed response, e.g. to close a changefeed.
+pub fn stop_query(conn: &mut Connection, token: u64) -> RdbResult<Response> {
+    const STOP: u8 = 3;
+    conn.send(token, json::Array(vec![STOP.to_json()]))
+}
+
 impl Connection {
     /// Sets the default database on this connection.
     pub fn use_db<S: StrAllocating>(&mut self, db: S) {
         self.opt_args.db = Some(db.into_string());
     }
 
+    /// Sets the `time_format` run option for queries on this connection.
+    pub fn time_format(&mut self, format: TimeFormat) {
+        self.opt_args.time_format = Some(format);
+    }
+
+    /// Sets the `binary_format` run option for queries on this connection.
+    pub fn binary_format(&mut self, format: BinaryFormat) {
+        self.opt_args.binary_format = Some(format);
+    }
+
     fn run(&mut self, term: Json) -> RdbResult<Response> {
+        let opt_args = (&self.opt_args).to_json();
+        self.start(term, opt_args)
+    }
+
+    fn start(&mut self, term: Json, opt_args: Json) -> RdbResult<Response> {
         const START: u8 = 1;
 
+        let token = self.token;
+        self.token += 1;
         let query_type = START;
-        let query = (query_type, term, &self.opt_args).to_json();
+        let query = (query_type, term, opt_args).to_json();
+        self.send(token, query)
+    }
 
-        let response_buf = try!(self.execute_json(query));
+    fn send(&mut self, token: u64, query: Json) -> RdbResult<Response> {
+        let response_buf = try!(self.execute_json(token, query));
         let response_json = {
             use std::io::MemReader;
             let mut reader = MemReader::new(response_buf);
@@ -68,18 +126,16 @@ impl Connection {
             json::from_reader(&mut reader).unwrap()
         };
 
-        let res = Response::from_json(response_json);
+        let res = Response::from_json(token, response_json);
         debug!("Response: {}", res);
         res
     }
 
-    fn execute_json(&mut self, json: Json) -> IoResult<Vec<u8>> {
-        self.execute_raw(json.to_string().as_bytes())
+    fn execute_json(&mut self, token: u64, json: Json) -> IoResult<Vec<u8>> {
+        self.execute_raw(token, json.to_string().as_bytes())
     }
 
-    fn execute_raw(&mut self, query: &[u8]) -> IoResult<Vec<u8>> {
-        let token = self.token;
-        self.token += 1;
+    fn execute_raw(&mut self, token: u64, query: &[u8]) -> IoResult<Vec<u8>> {
         let query_size = query.len();
         assert!(query_size <= ::std::i32::MAX as uint);
 
@@ -125,7 +181,9 @@ pub fn connect(host: &str, port: u16) -> RdbResult<Connection> {
         Ok(Connection {
             stream: BufferedStream::new(stream),
             opt_args: OptArgs {
-                db: None
+                db: None,
+                time_format: None,
+                binary_format: None
             },
             token: 0
         })
@@ -143,3 +201,34 @@ pub fn connect(host: &str, port: u16) -> RdbResult<Connection> {
     };
     Ok(conn)
 }
+
+#[cfg(test)]
+mod test {
+    use serialize::json::{mod, ToJson};
+
+    use query::{BinaryFormat, TimeFormat};
+    use super::OptArgs;
+
+    #[test]
+    fn test_opt_args_to_json() {
+        let opt_args = OptArgs { db: None, time_format: None, binary_format: None };
+        assert_eq!((&opt_args).to_json(), json::from_str("{}").unwrap());
+
+        let opt_args = OptArgs {
+            db: Some("foo".to_string()),
+            time_format: Some(TimeFormat::Raw),
+            binary_format: Some(BinaryFormat::Raw)
+        };
+        assert_eq!((&opt_args).to_json(),
+                   json::from_str(r#"{"db": [14, ["foo"]], "time_format": "raw",
+                                      "binary_format": "raw"}"#).unwrap());
+
+        let opt_args = OptArgs {
+            db: None,
+            time_format: Some(TimeFormat::Native),
+            binary_format: Some(BinaryFormat::Native)
+        };
+        assert_eq!((&opt_args).to_json(),
+                   json::from_str(r#"{"time_format": "native", "binary_format": "native"}"#).unwrap());
+    }
+}
@@ -7,7 +7,9 @@ use errors::Error::{mod, DriverError};
 pub enum ResponseKind {
     Atom,
     Sequence,
-    Partial
+    Partial,
+    /// A batch of changes from a changefeed, which never ends on its own.
+    Feed
 }
 
 #[deriving(Show)]
@@ -38,29 +40,35 @@ impl RawResponse {
 #[deriving(Show)]
 pub struct Response {
     pub kind: ResponseKind,
-    pub values: Json
+    pub values: Json,
+    /// The token of the query this responds to, for continuing it.
+    pub token: u64
 }
 
 const SUCCESS_ATOM: u8 = 1;
 const SUCCESS_SEQUENCE: u8 = 2;
 const SUCCESS_PARTIAL: u8 = 3;
+const SUCCESS_FEED: u8 = 5;
 
 impl Response {
-    pub fn from_json(json: Json) -> RdbResult<Response> {
+    pub fn from_json(token: u64, json: Json) -> RdbResult<Response> {
         RawResponse::from_json(json).and_then(|raw: RawResponse| {
-            match raw.res_type {
-                SUCCESS_ATOM => Ok(Response::new(ResponseKind::Atom, raw.res)),
-                SUCCESS_SEQUENCE => Ok(Response::new(ResponseKind::Sequence, raw.res)),
-                SUCCESS_PARTIAL => Ok(Response::new(ResponseKind::Partial, raw.res)),
-                n => Err(Error::from_code_res(n, raw.res))
-            }
+            let kind = match raw.res_type {
+                SUCCESS_ATOM => ResponseKind::Atom,
+                SUCCESS_SEQUENCE => ResponseKind::Sequence,
+                SUCCESS_PARTIAL => ResponseKind::Partial,
+                SUCCESS_FEED => ResponseKind::Feed,
+                n => return Err(Error::from_code_res(n, raw.res))
+            };
+            Ok(Response::new(kind, raw.res, token))
         })
     }
 
-    fn new(kind: ResponseKind, res: Json) -> Response {
+    fn new(kind: ResponseKind, res: Json, token: u64) -> Response {
         Response {
             kind: kind,
-            values: res
+            values: res,
+            token: token
         }
     }
 }
@@ -85,14 +93,22 @@ mod test {
     #[test]
     fn test_success_from_json() {
         let json = json::from_str(r#"{"t": 1, "r": [["bar","foo"]]}"#).unwrap();
-        let res = Response::from_json(json).unwrap();
+        let res = Response::from_json(7, json).unwrap();
         let tables = json::Array(vec![json::String("bar".to_string()),
                                       json::String("foo".to_string())]);
 
-        let Response { kind, values } = res;
+        let Response { kind, values, token } = res;
 
         assert_eq!(kind, ResponseKind::Atom);
         assert_eq!(values, json::Array(vec![tables]));
+        assert_eq!(token, 7);
+    }
+
+    #[test]
+    fn test_feed_from_json() {
+        let json = json::from_str(r#"{"t": 5, "r": [{"old_val": null, "new_val": {"id": 1}}]}"#).unwrap();
+        let res = Response::from_json(0, json).unwrap();
+        assert_eq!(res.kind, ResponseKind::Feed);
     }
 
 }
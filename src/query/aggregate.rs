@@ -0,0 +1,74 @@
+use std::collections::TreeMap;
+
+use serialize::json::Json;
+
+use super::cursor::Cursor;
+use super::term_type as ty;
+
+// Apart from distinct, these all take an optional field name or function to
+// aggregate by, hence the trailing args.
+
+query! {
+    Count -> u64 {
+        seq: Json ; ..args
+    } ty::COUNT
+}
+
+pub fn count(seq: Json, args: Vec<Json>) -> Count {
+    Count { seq: seq, args: args }
+}
+
+query! {
+    Sum -> f64 {
+        seq: Json ; ..args
+    } ty::SUM
+}
+
+pub fn sum(seq: Json, args: Vec<Json>) -> Sum {
+    Sum { seq: seq, args: args }
+}
+
+query! {
+    Avg -> f64 {
+        seq: Json ; ..args
+    } ty::AVG
+}
+
+pub fn avg(seq: Json, args: Vec<Json>) -> Avg {
+    Avg { seq: seq, args: args }
+}
+
+query! {
+    Min -> Json {
+        seq: Json ; ..args
+    } opt_args { index } ty::MIN
+}
+
+pub fn min(seq: Json, args: Vec<Json>) -> Min {
+    Min { seq: seq, args: args, opt_args: TreeMap::new() }
+}
+
+query! {
+    Max -> Json {
+        seq: Json ; ..args
+    } opt_args { index } ty::MAX
+}
+
+pub fn max(seq: Json, args: Vec<Json>) -> Max {
+    Max { seq: seq, args: args, opt_args: TreeMap::new() }
+}
+
+term! {
+    Distinct {
+        seq: Json
+    } opt_args { index } ty::DISTINCT
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Distinct {
+}
+
+pub fn distinct(seq: Json) -> Distinct {
+    Distinct { seq: seq, opt_args: TreeMap::new() }
+}
+
+impl_expr! { Count, Sum, Avg, Min, Max, Distinct }
@@ -0,0 +1,124 @@
+use std::collections::TreeMap;
+
+use serialize::{Decodable, Decoder};
+use serialize::base64::{FromBase64, ToBase64, STANDARD};
+use serialize::json::{mod, Json, ToJson};
+
+use errors::RdbResult;
+use from_response::{mod, FromResponse};
+use net::{Connection, Response};
+
+use super::ToTerm;
+use super::term_type as ty;
+
+/// Binary data, as RethinkDB's `BINARY` pseudo-type: the bytes are sent and
+/// received base64 encoded. Like `Time`, it's decoded wherever a `Binary` is
+/// expected, and left as a pseudo-type object within `Json` results.
+#[deriving(Clone, Show, PartialEq)]
+pub struct Binary(pub Vec<u8>);
+
+impl Binary {
+    pub fn as_slice(&self) -> &[u8] {
+        let Binary(ref data) = *self;
+        data.as_slice()
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        let Binary(data) = self;
+        data
+    }
+}
+
+impl ToJson for Binary {
+    fn to_json(&self) -> Json {
+        let mut d = TreeMap::new();
+        d.insert("$reql_type$".to_string(), "BINARY".to_string().to_json());
+        d.insert("data".to_string(), self.as_slice().to_base64(STANDARD).to_json());
+        json::Object(d)
+    }
+}
+
+impl ToTerm for Binary {
+    fn to_term(&self) -> Json {
+        self.to_json()
+    }
+}
+
+impl<E, D: Decoder<E>> Decodable<D, E> for Binary {
+    fn decode(d: &mut D) -> Result<Binary, E> {
+        d.read_struct("Binary", 2, |d| {
+            let reql_type: String = try!(d.read_struct_field("$reql_type$", 0, |d| Decodable::decode(d)));
+            if reql_type.as_slice() != "BINARY" {
+                return Err(d.error(format!("expected a BINARY, got {}", reql_type).as_slice()));
+            }
+            let data: String = try!(d.read_struct_field("data", 1, |d| Decodable::decode(d)));
+            match data.as_slice().from_base64() {
+                Ok(bytes) => Ok(Binary(bytes)),
+                Err(e) => Err(d.error(format!("invalid BINARY data: {}", e).as_slice()))
+            }
+        })
+    }
+}
+
+impl<'a> FromResponse<'a> for Binary {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Binary> {
+        from_response::decode(try!(from_response::atom(res)))
+    }
+}
+
+/// The `binary_format` run option; see `Connection::binary_format`. As with
+/// `TimeFormat`, binary data arrives as the `BINARY` pseudo-type in both
+/// formats, so it decodes as `Binary` either way, and `Binary::to_json` gives
+/// back the pseudo-type object.
+#[deriving(Clone, Show, PartialEq)]
+pub enum BinaryFormat {
+    Native,
+    Raw
+}
+
+impl ToJson for BinaryFormat {
+    fn to_json(&self) -> Json {
+        match *self {
+            BinaryFormat::Native => "native".to_string().to_json(),
+            BinaryFormat::Raw => "raw".to_string().to_json()
+        }
+    }
+}
+
+query! {
+    BinaryTerm -> Binary {
+        data: Json
+    } ty::BINARY
+}
+
+/// Converts a string term to binary. To send bytes from Rust, use a `Binary`
+/// value directly.
+pub fn binary<T: ToTerm>(data: T) -> BinaryTerm {
+    BinaryTerm { data: data.to_term() }
+}
+
+impl_expr! { BinaryTerm }
+
+#[cfg(test)]
+mod test {
+    use from_response;
+    use serialize::json::{mod, ToJson};
+
+    use super::Binary;
+
+    #[test]
+    fn test_binary_round_trip() {
+        let image = include_bin!("../../jay_sherman.jpg").to_vec();
+        let datum = Binary(image.clone()).to_json();
+        let binary: Binary = from_response::decode(datum).unwrap();
+        assert_eq!(binary.into_vec(), image);
+
+        let datum = json::from_str(r#"{"$reql_type$": "BINARY", "data": "aXQgc3RpbmtzIQ=="}"#).unwrap();
+        let binary: Binary = from_response::decode(datum.clone()).unwrap();
+        assert_eq!(binary.as_slice(), "it stinks!".as_bytes());
+        assert_eq!(binary.to_json(), datum);
+
+        let datum = json::from_str(r#"{"$reql_type$": "BINARY", "data": "not base64!"}"#).unwrap();
+        assert!(from_response::decode::<Binary>(datum).is_err());
+    }
+}
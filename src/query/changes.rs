@@ -0,0 +1,150 @@
+use std::vec;
+
+use serialize::Decodable;
+use serialize::json::{mod, Json};
+
+use errors::RdbResult;
+use from_response::{mod, FromResponse};
+use net::{mod, Connection, Response, ResponseKind};
+
+use super::term_type as ty;
+
+term! {
+    Changes {
+        seq: Json
+    } ty::CHANGES
+}
+
+impl<'a, T> ::query::Query<'a, ChangeFeed<'a, T>> for Changes
+    where T: Decodable<json::Decoder, json::DecoderError> {
+}
+
+pub fn changes(seq: Json) -> Changes {
+    Changes { seq: seq }
+}
+
+/// A change to a document: its value before and after. `old_val` is `None`
+/// for inserted documents, and `new_val` for deleted ones.
+#[deriving(Decodable, Show, PartialEq)]
+pub struct Change<T> {
+    pub old_val: Option<T>,
+    pub new_val: Option<T>
+}
+
+/// The changes to a table or selection, as they happen.
+///
+/// A changefeed never ends on its own: iterating it blocks until the next
+/// change arrives, fetching each batch with a CONTINUE on the feed's query.
+/// Call `stop` (or drop the feed) to close it on the server, which frees the
+/// connection for other queries.
+pub struct ChangeFeed<'a, T> {
+    conn: &'a mut Connection,
+    token: u64,
+    batch: vec::MoveItems<Json>,
+    done: bool
+}
+
+// Splits a response into its batch of changes and whether it's the last one.
+fn unpack(res: Response) -> RdbResult<(Vec<Json>, bool)> {
+    use errors::Error::DriverError;
+
+    let done = match res.kind {
+        ResponseKind::Feed | ResponseKind::Partial => false,
+        ResponseKind::Sequence => true,
+        ResponseKind::Atom => {
+            return Err(DriverError(format!("expected a changefeed, got {}", res)))
+        }
+    };
+    match res.values {
+        json::Array(values) => Ok((values, done)),
+        values => Err(DriverError(format!("expected a batch of changes, got {}", values)))
+    }
+}
+
+impl<'a, T> ChangeFeed<'a, T> {
+    /// Closes the changefeed. Dropping it does the same, ignoring any error.
+    pub fn stop(&mut self) -> RdbResult<()> {
+        if !self.done {
+            self.done = true;
+            self.batch = Vec::new().into_iter();
+            try!(net::stop_query(&mut *self.conn, self.token));
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> FromResponse<'a> for ChangeFeed<'a, T> {
+    fn from_response(res: Response, conn: &'a mut Connection) -> RdbResult<ChangeFeed<'a, T>> {
+        let token = res.token;
+        let (batch, done) = try!(unpack(res));
+        Ok(ChangeFeed {
+            conn: conn,
+            token: token,
+            batch: batch.into_iter(),
+            done: done
+        })
+    }
+}
+
+impl<'a, T> Iterator<RdbResult<Change<T>>> for ChangeFeed<'a, T>
+    where T: Decodable<json::Decoder, json::DecoderError> {
+    fn next(&mut self) -> Option<RdbResult<Change<T>>> {
+        loop {
+            if let Some(change) = self.batch.next() {
+                return Some(from_response::decode(change));
+            }
+            if self.done {
+                return None;
+            }
+            let next = net::continue_query(&mut *self.conn, self.token).and_then(|res| unpack(res));
+            match next {
+                Ok((batch, done)) => {
+                    self.batch = batch.into_iter();
+                    self.done = done;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[unsafe_destructor]
+impl<'a, T> Drop for ChangeFeed<'a, T> {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use from_response;
+    use serialize::json;
+
+    use super::Change;
+
+    #[deriving(Decodable, Show, PartialEq)]
+    struct Score {
+        id: String,
+        score: u64
+    }
+
+    #[test]
+    fn test_change_decode() {
+        let datum = json::from_str(r#"{"old_val": {"id": "bob", "score": 1},
+                                       "new_val": {"id": "bob", "score": 2}}"#).unwrap();
+        let change: Change<Score> = from_response::decode(datum).unwrap();
+        assert_eq!(change.old_val, Some(Score { id: "bob".to_string(), score: 1 }));
+        assert_eq!(change.new_val, Some(Score { id: "bob".to_string(), score: 2 }));
+
+        let datum = json::from_str(r#"{"old_val": null, "new_val": {"id": "sally", "score": 0}}"#).unwrap();
+        let change: Change<Score> = from_response::decode(datum).unwrap();
+        assert_eq!(change.old_val, None);
+
+        let datum = json::from_str(r#"{"new_val": {"id": "sally", "score": 0}}"#).unwrap();
+        let change: Change<Score> = from_response::decode(datum).unwrap();
+        assert_eq!(change.old_val, None);
+    }
+}
@@ -0,0 +1,77 @@
+use serialize::json::Json;
+
+use super::ToTerm;
+use super::term_type as ty;
+
+query! {
+    Branch -> Json {
+        test: Json,
+        true_branch: Json,
+        false_branch: Json
+    } ty::BRANCH
+}
+
+/// Evaluates `true_branch` if `test` is anything but `false` or `null`, and
+/// `false_branch` otherwise, i.e. ReQL's `branch`.
+pub fn branch<T: ToTerm, A: ToTerm, B: ToTerm>(test: T, true_branch: A, false_branch: B) -> Branch {
+    Branch {
+        test: test.to_term(),
+        true_branch: true_branch.to_term(),
+        false_branch: false_branch.to_term()
+    }
+}
+
+query! {
+    ErrorTerm -> Json {
+        message: String
+    } ty::ERROR
+}
+
+/// Aborts the query with the given message.
+pub fn error(message: &str) -> ErrorTerm {
+    ErrorTerm { message: message.into_string() }
+}
+
+query! {
+    DefaultTerm -> Json {
+        value: Json,
+        default: Json
+    } ty::DEFAULT
+}
+
+pub fn default(value: Json, default: Json) -> DefaultTerm {
+    DefaultTerm { value: value, default: default }
+}
+
+query! {
+    CoerceTo -> Json {
+        value: Json,
+        type_name: String
+    } ty::COERCE_TO
+}
+
+pub fn coerce_to(value: Json, type_name: &str) -> CoerceTo {
+    CoerceTo { value: value, type_name: type_name.into_string() }
+}
+
+query! {
+    TypeOf -> String {
+        value: Json
+    } ty::TYPE_OF
+}
+
+pub fn type_of(value: Json) -> TypeOf {
+    TypeOf { value: value }
+}
+
+query! {
+    Info -> Json {
+        value: Json
+    } ty::INFO
+}
+
+pub fn info(value: Json) -> Info {
+    Info { value: value }
+}
+
+impl_expr! { Branch, ErrorTerm, DefaultTerm, CoerceTo, TypeOf, Info }
@@ -1,8 +1,9 @@
 use errors::RdbResult;
-use from_response::FromResponse;
+use from_response::{mod, FromResponse};
 use net::{Connection, Response};
 
-use serialize::json::Json;
+use serialize::Decodable;
+use serialize::json::{mod, Json};
 use std::iter::Iterator;
 use std::{slice, vec};
 
@@ -47,8 +48,21 @@ impl<'a> FromResponse<'a> for Cursor<'a> {
                     conn: Some(conn)
                 })
             }
+            ResponseKind::Feed => {
+                Err(DriverError("changefeeds can't be read as a Cursor; run them as a ChangeFeed".into_string()))
+            }
             ResponseKind::Atom => {
-                Err(DriverError("unexpected SUCCESS_ATOM".into_string()))
+                // Sequence operations on arrays (as opposed to streams) come
+                // back as a single array datum
+                match chunk.as_slice() {
+                    [json::Array(ref items)] => {
+                        Ok(Cursor {
+                            chunk: items.clone(),
+                            conn: None
+                        })
+                    }
+                    _ => Err(DriverError("unexpected SUCCESS_ATOM".into_string()))
+                }
             }
         }
     }
@@ -68,6 +82,15 @@ impl<'a> Cursor<'a> {
             None => MoveItems { items: self.chunk.into_iter() }
         }
     }
+
+    /// Decodes every document into a Rust value.
+    pub fn decode<T: Decodable<json::Decoder, json::DecoderError>>(self) -> RdbResult<Vec<T>> {
+        let mut values = Vec::new();
+        for x in self.into_iter() {
+            values.push(try!(from_response::decode(x)));
+        }
+        Ok(values)
+    }
 }
 
 impl<'a> Iterator<&'a Json> for Items<'a> {
@@ -129,4 +152,18 @@ mod test {
         let fix = fixture();
         assert_eq!(fix.peeps, fix.cursor.into_iter().collect())
     }
+
+    #[deriving(Decodable, PartialEq, Show)]
+    struct DecodedPerson {
+        name: String,
+        age: i32
+    }
+
+    #[test]
+    fn test_decode() {
+        let fix = fixture();
+        let peeps: Vec<DecodedPerson> = fix.cursor.decode().unwrap();
+        assert_eq!(peeps, vec![DecodedPerson { name: "bob".to_string(), age: 23 },
+                               DecodedPerson { name: "sally".to_string(), age: 25 }]);
+    }
 }
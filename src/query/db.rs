@@ -1,3 +1,6 @@
+use std::collections::TreeMap;
+
+use super::ddl::{Created, Dropped};
 use super::table::{Table, TableCreate, TableDrop, TableList};
 use super::term_type as ty;
 
@@ -29,7 +32,7 @@ impl Db {
     }
 
     pub fn table_create(self, name: &str) -> TableCreate {
-        TableCreate::TableCreate2 { name: name.into_string(), db: self }
+        TableCreate::TableCreate2 { name: name.into_string(), db: self, opt_args: TreeMap::new() }
     }
 
     pub fn table_drop(self, name: &str) -> TableDrop {
@@ -42,13 +45,13 @@ impl Db {
 }
 
 query! {
-    DbCreate -> () {
+    DbCreate -> Created {
         name: String
     } ty::DB_CREATE
 }
 
 query! {
-    DbDrop -> () {
+    DbDrop -> Dropped {
         name: String
     } ty::DB_DROP
 }
@@ -56,3 +59,7 @@ query! {
 query! {
     DbList -> Vec<String> ; ty::DB_LIST
 }
+
+// Db is deliberately left out: a database isn't a value, so none of the Expr
+// methods apply to it.
+impl_expr! { DbCreate, DbDrop, DbList }
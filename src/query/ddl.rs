@@ -0,0 +1,160 @@
+use serialize::json::{mod, Json};
+
+use errors::RdbResult;
+use errors::Error::DriverError;
+use from_response::{mod, FromResponse};
+use net::{Connection, Response};
+
+use super::changes::Change;
+
+/// The result of creating a database, table or index.
+#[deriving(Show, PartialEq)]
+pub struct Created {
+    /// How many were created: 0 or 1.
+    pub created: u64,
+    /// The resulting changes to the cluster's configuration, for servers
+    /// which report them.
+    pub config_changes: Vec<Change<Json>>
+}
+
+/// The result of dropping a database, table or index.
+#[deriving(Show, PartialEq)]
+pub struct Dropped {
+    /// How many were dropped: 0 or 1.
+    pub dropped: u64,
+    /// The resulting changes to the cluster's configuration, for servers
+    /// which report them.
+    pub config_changes: Vec<Change<Json>>
+}
+
+/// The result of renaming an index.
+#[deriving(Show, PartialEq)]
+pub struct Renamed {
+    /// How many were renamed: 0 if the new name is the same as the old, else
+    /// 1.
+    pub renamed: u64
+}
+
+// Newer servers count databases and tables separately, e.g. a db_create
+// result is {"dbs_created": 1, "config_changes": [...]} rather than
+// {"created": 1}, so take whichever count is there.
+fn count(datum: &Json, keys: &[&str]) -> RdbResult<u64> {
+    for key in keys.iter() {
+        if let Some(n) = datum.find(*key).and_then(|n| n.as_u64()) {
+            return Ok(n);
+        }
+    }
+    Err(DriverError(format!("expected one of {} in {}", keys, datum)))
+}
+
+fn config_changes(datum: &Json) -> RdbResult<Vec<Change<Json>>> {
+    fn val(change: &Json, key: &str) -> Option<Json> {
+        match change.find(key) {
+            Some(&json::Null) | None => None,
+            Some(val) => Some(val.clone())
+        }
+    }
+
+    let changes = match datum.find("config_changes") {
+        Some(changes) => changes,
+        None => return Ok(vec![])
+    };
+    match changes.as_array() {
+        Some(changes) => Ok(changes.iter().map(|change| {
+            Change { old_val: val(change, "old_val"), new_val: val(change, "new_val") }
+        }).collect()),
+        None => Err(DriverError(format!("expected config_changes to be an array, got {}", changes)))
+    }
+}
+
+impl Created {
+    pub fn from_json(datum: Json) -> RdbResult<Created> {
+        Ok(Created {
+            created: try!(count(&datum, &["created", "dbs_created", "tables_created"])),
+            config_changes: try!(config_changes(&datum))
+        })
+    }
+}
+
+impl Dropped {
+    pub fn from_json(datum: Json) -> RdbResult<Dropped> {
+        Ok(Dropped {
+            dropped: try!(count(&datum, &["dropped", "dbs_dropped", "tables_dropped"])),
+            config_changes: try!(config_changes(&datum))
+        })
+    }
+}
+
+impl Renamed {
+    pub fn from_json(datum: Json) -> RdbResult<Renamed> {
+        Ok(Renamed { renamed: try!(count(&datum, &["renamed"])) })
+    }
+}
+
+impl<'a> FromResponse<'a> for Created {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Created> {
+        Created::from_json(try!(from_response::atom(res)))
+    }
+}
+
+impl<'a> FromResponse<'a> for Dropped {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Dropped> {
+        Dropped::from_json(try!(from_response::atom(res)))
+    }
+}
+
+impl<'a> FromResponse<'a> for Renamed {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Renamed> {
+        Renamed::from_json(try!(from_response::atom(res)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serialize::json;
+
+    use super::{Created, Dropped, Renamed};
+
+    #[test]
+    fn test_created() {
+        let datum = json::from_str(r#"{"created": 1}"#).unwrap();
+        let created = Created::from_json(datum).unwrap();
+        assert_eq!(created.created, 1);
+        assert!(created.config_changes.is_empty());
+
+        let datum = json::from_str(r#"{"dbs_created": 1, "config_changes":
+                                       [{"old_val": null, "new_val": {"id": "abc", "name": "foo"}}]}"#).unwrap();
+        let created = Created::from_json(datum).unwrap();
+        assert_eq!(created.created, 1);
+        assert_eq!(created.config_changes.len(), 1);
+        assert_eq!(created.config_changes[0].old_val, None);
+        assert_eq!(created.config_changes[0].new_val, Some(json!({"id": "abc", "name": "foo"})));
+
+        let datum = json::from_str(r#"{"inserted": 1}"#).unwrap();
+        assert!(Created::from_json(datum).is_err());
+    }
+
+    #[test]
+    fn test_dropped() {
+        let datum = json::from_str(r#"{"dropped": 1}"#).unwrap();
+        assert_eq!(Dropped::from_json(datum).unwrap().dropped, 1);
+
+        let datum = json::from_str(r#"{"dbs_dropped": 1, "tables_dropped": 3, "config_changes":
+                                       [{"old_val": {"id": "abc", "name": "foo"}, "new_val": null}]}"#).unwrap();
+        let dropped = Dropped::from_json(datum).unwrap();
+        assert_eq!(dropped.dropped, 1);
+        assert_eq!(dropped.config_changes[0].new_val, None);
+    }
+
+    #[test]
+    fn test_renamed() {
+        let datum = json::from_str(r#"{"renamed": 1}"#).unwrap();
+        assert_eq!(Renamed::from_json(datum).unwrap(), Renamed { renamed: 1 });
+
+        let datum = json::from_str(r#"{"renamed": 0}"#).unwrap();
+        assert_eq!(Renamed::from_json(datum).unwrap().renamed, 0);
+
+        let datum = json::from_str(r#"{"dropped": 1}"#).unwrap();
+        assert!(Renamed::from_json(datum).is_err());
+    }
+}
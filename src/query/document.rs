@@ -0,0 +1,221 @@
+use serialize::json::{Json, JsonObject};
+
+use super::ToTerm;
+
+use super::term_type as ty;
+
+query! {
+    Merge -> Json {
+        obj: Json,
+        other: Json
+    } ty::MERGE
+}
+
+pub fn merge(obj: Json, other: Json) -> Merge {
+    Merge { obj: obj, other: other }
+}
+
+query! {
+    Append -> Json {
+        array: Json,
+        value: Json
+    } ty::APPEND
+}
+
+pub fn append(array: Json, value: Json) -> Append {
+    Append { array: array, value: value }
+}
+
+query! {
+    Prepend -> Json {
+        array: Json,
+        value: Json
+    } ty::PREPEND
+}
+
+pub fn prepend(array: Json, value: Json) -> Prepend {
+    Prepend { array: array, value: value }
+}
+
+query! {
+    Difference -> Json {
+        array: Json,
+        other: Json
+    } ty::DIFFERENCE
+}
+
+pub fn difference(array: Json, other: Json) -> Difference {
+    Difference { array: array, other: other }
+}
+
+query! {
+    SetInsert -> Json {
+        array: Json,
+        value: Json
+    } ty::SET_INSERT
+}
+
+pub fn set_insert(array: Json, value: Json) -> SetInsert {
+    SetInsert { array: array, value: value }
+}
+
+query! {
+    SetIntersection -> Json {
+        array: Json,
+        other: Json
+    } ty::SET_INTERSECTION
+}
+
+pub fn set_intersection(array: Json, other: Json) -> SetIntersection {
+    SetIntersection { array: array, other: other }
+}
+
+query! {
+    SetUnion -> Json {
+        array: Json,
+        other: Json
+    } ty::SET_UNION
+}
+
+pub fn set_union(array: Json, other: Json) -> SetUnion {
+    SetUnion { array: array, other: other }
+}
+
+query! {
+    SetDifference -> Json {
+        array: Json,
+        other: Json
+    } ty::SET_DIFFERENCE
+}
+
+pub fn set_difference(array: Json, other: Json) -> SetDifference {
+    SetDifference { array: array, other: other }
+}
+
+query! {
+    InsertAt -> Json {
+        array: Json,
+        index: int,
+        value: Json
+    } ty::INSERT_AT
+}
+
+pub fn insert_at(array: Json, index: int, value: Json) -> InsertAt {
+    InsertAt { array: array, index: index, value: value }
+}
+
+query! {
+    DeleteAt -> Json {
+        array: Json ; ..range
+    } ty::DELETE_AT
+}
+
+pub fn delete_at(array: Json, range: Vec<Json>) -> DeleteAt {
+    DeleteAt { array: array, range: range }
+}
+
+query! {
+    ChangeAt -> Json {
+        array: Json,
+        index: int,
+        value: Json
+    } ty::CHANGE_AT
+}
+
+pub fn change_at(array: Json, index: int, value: Json) -> ChangeAt {
+    ChangeAt { array: array, index: index, value: value }
+}
+
+query! {
+    SpliceAt -> Json {
+        array: Json,
+        index: int,
+        values: Json
+    } ty::SPLICE_AT
+}
+
+pub fn splice_at(array: Json, index: int, values: Json) -> SpliceAt {
+    SpliceAt { array: array, index: index, values: values }
+}
+
+/// Replaces a nested object outright when used as a field value in `update`
+/// or `merge`, rather than merging into it.
+///
+/// The value must be built with `make_obj`, not `json!`: a `Json` value is
+/// encoded as plain data, which would send the literal as an array rather than
+/// as a term.
+pub fn literal<T: ToTerm>(value: T) -> Literal {
+    Literal { value: vec![value.to_term()] }
+}
+
+/// Removes the field it's the value of in `update` or `merge`. The same
+/// `make_obj` caveat as for `literal` applies.
+pub fn empty_literal() -> Literal {
+    Literal { value: vec![] }
+}
+
+query! {
+    Literal -> Json {
+        ; ..value
+    } ty::LITERAL
+}
+
+/// An object whose field values may be ReQL terms as well as plain data, e.g.
+/// `r::make_obj().field("name", "bob").field("rank", r::table("ranks").count())`
+pub struct MakeObj {
+    fields: JsonObject
+}
+
+pub fn make_obj() -> MakeObj {
+    MakeObj { fields: JsonObject::new() }
+}
+
+impl MakeObj {
+    pub fn field<T: ToTerm>(mut self, name: &str, value: T) -> MakeObj {
+        self.fields.insert(name.into_string(), value.to_term());
+        self
+    }
+}
+
+// MAKE_OBJ takes no positional arguments; the object itself is sent as the
+// optional arguments, i.e. [3, [], {...}]
+impl ::query::Term for MakeObj {
+    fn args(&self) -> Vec<Json> {
+        vec![]
+    }
+
+    fn opt_args(&self) -> Option<JsonObject> {
+        Some(self.fields.clone())
+    }
+}
+
+to_json_impl! { MakeObj ty::MAKE_OBJ }
+
+impl<'a> ::query::Query<'a, Json> for MakeObj {
+}
+
+/// An array whose elements may be ReQL terms of differing types, e.g.
+/// `r::make_array().push(1i32).push(r::table("foo").count())`
+pub fn make_array() -> MakeArray {
+    MakeArray { items: vec![] }
+}
+
+query! {
+    MakeArray -> Json {
+        ; ..items
+    } ty::MAKE_ARRAY
+}
+
+impl MakeArray {
+    pub fn push<T: ToTerm>(mut self, item: T) -> MakeArray {
+        self.items.push(item.to_term());
+        self
+    }
+}
+
+impl_expr! { Literal, MakeObj, MakeArray }
+
+impl_expr! {
+    Merge, Append, Prepend, Difference, SetInsert, SetIntersection, SetUnion,
+    SetDifference, InsertAt, DeleteAt, ChangeAt, SpliceAt
+}
@@ -1,6 +1,24 @@
-use serialize::json::{Json, ToJson};
-use std::ops;
-use query::term_type as ty;
+use serialize::json::{mod, Json, ToJson};
+
+use super::aggregate::{mod, Count, Sum, Avg, Min, Max, Distinct};
+use super::changes::{mod, Changes};
+use super::control::{mod, DefaultTerm, CoerceTo, TypeOf, Info};
+use super::document::{mod, Merge, Append, Prepend, Difference};
+use super::document::{SetInsert, SetIntersection, SetUnion, SetDifference};
+use super::document::{InsertAt, DeleteAt, ChangeAt, SpliceAt};
+use super::fields::{mod, GetField, Bracket, Pluck, Without, HasFields, WithFields, Keys};
+use super::fields::Pathspec;
+use super::math::{mod, Add, Sub, Mul, Div, Mod, EqTerm, Ne, Lt, Le, Gt, Ge, NotTerm, And, Or};
+use super::func::{Var, func, func2};
+use super::geo::{mod, ToGeoJson, Distance, Intersects, Includes, Fill, PolygonSub};
+use super::group::{mod, Group};
+use super::join::{mod, InnerJoin, OuterJoin, EqJoin, Zip};
+use super::sequence::{mod, OrderBy, Limit, Skip, Slice, Nth, Sample, IsEmpty};
+use super::sequence::{Map, ConcatMap, Reduce, ForEach, Filter, Contains, IndexesOf};
+use super::string::{mod, Match, Split, Upcase, Downcase};
+use super::term_type as ty;
+use super::time::{mod, ToIso8601, ToEpochTime, InTimezone, During, Date, Timezone, TimeOfDay};
+use super::time::{Year, Month, Day, DayOfWeek, DayOfYear, Hours, Minutes, Seconds};
 
 // NOTE: Would a phantom type on Datum and the other structs in this module
 // improve safety enough to justify the complexity and loss of expressiveness?
@@ -14,8 +32,29 @@ impl ToJson for Datum {
     }
 }
 
-pub fn expr<T: ToJson>(x: T) -> Datum {
-    Datum(x.to_json())
+/// Encodes plain JSON for use as a term. The server reads every JSON array as
+/// a term, so arrays in the data have to be wrapped in MAKE_ARRAY, i.e.
+/// `[1, 2]` is sent as `[2, [1, 2]]`. Object values are read as terms too,
+/// so objects are walked recursively. Only use this on data: the JSON of a
+/// term would be wrapped too.
+pub fn datum(x: &Json) -> Json {
+    match *x {
+        json::Array(ref items) => {
+            let items: Vec<Json> = items.iter().map(|x| datum(x)).collect();
+            (ty::MAKE_ARRAY, items).to_json()
+        }
+        json::Object(ref obj) => {
+            json::Object(obj.iter().map(|(k, v)| (k.clone(), datum(v))).collect())
+        }
+        _ => x.clone()
+    }
+}
+
+/// Turns a value into a term. Plain values and JSON are encoded as data (see
+/// `datum`), while terms are passed through as they are, so
+/// `r::expr(vec![r::table("foo"), r::table("bar")])` is an array of tables.
+pub fn expr<T: ToTerm>(x: T) -> Datum {
+    Datum(x.to_term())
 }
 
 // We could impl Query here but Datum queries are weird and non-standard e.g.
@@ -25,36 +64,624 @@ pub fn expr<T: ToJson>(x: T) -> Datum {
 // In practice this probably isn't a huge deal. Who wants to use RethinkDB as
 // the identity function anyway?
 
-query! {
-    Add -> Json { // NOTE: we know it's Number -> Number or String -> String, so...
-        lhs: Json,
-        rhs: Json
-    } ty::ADD
+impl_expr! { Datum }
+
+/// Values which can be used as the arguments of ReQL terms: plain Rust values,
+/// JSON, and other terms.
+pub trait ToTerm {
+    fn to_term(&self) -> Json;
 }
 
-impl<T: ToJson> ops::Add<T, Add> for Datum {
-    fn add(&self, rhs: &T) -> Add {
-        Add {
-            lhs: self.to_json(),
-            rhs: rhs.to_json()
+macro_rules! to_term_impl {
+    ($($ty:ty),+) => {$(
+        impl ToTerm for $ty {
+            fn to_term(&self) -> Json {
+                self.to_json()
+            }
         }
+    )+}
+}
+
+to_term_impl! { bool, int, i8, i16, i32, i64, uint, u8, u16, u32, u64, f32, f64, String }
+
+// JSON is always data. Pass a term itself, rather than its `to_json()`, to use
+// it as an argument.
+impl ToTerm for Json {
+    fn to_term(&self) -> Json {
+        datum(self)
+    }
+}
+
+impl<'a> ToTerm for &'a str {
+    fn to_term(&self) -> Json {
+        json::String(self.to_string())
+    }
+}
+
+impl<T: ToTerm> ToTerm for Vec<T> {
+    fn to_term(&self) -> Json {
+        let items: Vec<Json> = self.iter().map(|x| x.to_term()).collect();
+        (ty::MAKE_ARRAY, items).to_json()
+    }
+}
+
+/// The ReQL methods shared by every term that evaluates to a value.
+///
+/// Any of these can be chained onto any expression, whatever its concrete
+/// type; like the other drivers, we leave it to the server to reject methods
+/// which don't make sense for the value at hand. Each method still returns
+/// its own term type, so `run` decodes into the right result.
+pub trait Expr: ToJson {
+    fn add<T: ToTerm>(self, rhs: T) -> Add {
+        math::add(self.to_json(), rhs.to_term())
+    }
+
+    fn sub<T: ToTerm>(self, rhs: T) -> Sub {
+        math::sub(self.to_json(), rhs.to_term())
+    }
+
+    fn mul<T: ToTerm>(self, rhs: T) -> Mul {
+        math::mul(self.to_json(), rhs.to_term())
+    }
+
+    fn div<T: ToTerm>(self, rhs: T) -> Div {
+        math::div(self.to_json(), rhs.to_term())
+    }
+
+    /// ReQL's `mod`.
+    fn rem<T: ToTerm>(self, rhs: T) -> Mod {
+        math::rem(self.to_json(), rhs.to_term())
+    }
+
+    /// ReQL's `eq`. Named like this rather than overloading `==`, which has to
+    /// return a plain bool.
+    fn eq<T: ToTerm>(self, rhs: T) -> EqTerm {
+        math::eq(self.to_json(), rhs.to_term())
+    }
+
+    fn ne<T: ToTerm>(self, rhs: T) -> Ne {
+        math::ne(self.to_json(), rhs.to_term())
+    }
+
+    fn lt<T: ToTerm>(self, rhs: T) -> Lt {
+        math::lt(self.to_json(), rhs.to_term())
+    }
+
+    fn le<T: ToTerm>(self, rhs: T) -> Le {
+        math::le(self.to_json(), rhs.to_term())
+    }
+
+    fn gt<T: ToTerm>(self, rhs: T) -> Gt {
+        math::gt(self.to_json(), rhs.to_term())
+    }
+
+    fn ge<T: ToTerm>(self, rhs: T) -> Ge {
+        math::ge(self.to_json(), rhs.to_term())
+    }
+
+    fn not(self) -> NotTerm {
+        math::not(self.to_json())
+    }
+
+    fn and<T: ToTerm>(self, rhs: T) -> And {
+        math::and(self.to_json(), rhs.to_term())
+    }
+
+    fn or<T: ToTerm>(self, rhs: T) -> Or {
+        math::or(self.to_json(), rhs.to_term())
+    }
+
+    /// Gets a single field from an object, or from every object in a sequence.
+    fn get_field(self, field: &str) -> GetField {
+        fields::get_field(self.to_json(), field)
+    }
+
+    /// Gets a field from an object, or an element from an array, as with
+    /// `obj("field")` and `arr(1)` in the other drivers.
+    fn bracket<T: ToTerm>(self, attr: T) -> Bracket {
+        fields::bracket(self.to_json(), attr.to_term())
+    }
+
+    /// Keeps only the selected fields.
+    fn pluck<P: Pathspec>(self, selectors: &[P]) -> Pluck {
+        fields::pluck(self.to_json(), selectors)
+    }
+
+    /// Drops the selected fields.
+    fn without<P: Pathspec>(self, selectors: &[P]) -> Without {
+        fields::without(self.to_json(), selectors)
+    }
+
+    /// Tests whether an object has all of the selected fields. On a sequence,
+    /// filters it down to the objects which do.
+    fn has_fields<P: Pathspec>(self, selectors: &[P]) -> HasFields {
+        fields::has_fields(self.to_json(), selectors)
+    }
+
+    /// Equivalent to `has_fields` followed by `pluck` on a sequence.
+    fn with_fields<P: Pathspec>(self, selectors: &[P]) -> WithFields {
+        fields::with_fields(self.to_json(), selectors)
+    }
+
+    fn keys(self) -> Keys {
+        fields::keys(self.to_json())
+    }
+
+    /// Merges another object into this one, with the other object's fields
+    /// taking precedence. Nested objects are merged recursively; use
+    /// `r::literal` to replace one outright. On a sequence, merges into each
+    /// element.
+    fn merge<T: ToTerm>(self, other: T) -> Merge {
+        document::merge(self.to_json(), other.to_term())
+    }
+
+    fn append<T: ToTerm>(self, value: T) -> Append {
+        document::append(self.to_json(), value.to_term())
+    }
+
+    fn prepend<T: ToTerm>(self, value: T) -> Prepend {
+        document::prepend(self.to_json(), value.to_term())
+    }
+
+    /// Removes every occurrence of the elements of `other` from an array.
+    fn difference<T: ToTerm>(self, other: T) -> Difference {
+        document::difference(self.to_json(), other.to_term())
+    }
+
+    /// Adds a value to an array, treating it as a set.
+    fn set_insert<T: ToTerm>(self, value: T) -> SetInsert {
+        document::set_insert(self.to_json(), value.to_term())
+    }
+
+    fn set_intersection<T: ToTerm>(self, other: T) -> SetIntersection {
+        document::set_intersection(self.to_json(), other.to_term())
+    }
+
+    fn set_union<T: ToTerm>(self, other: T) -> SetUnion {
+        document::set_union(self.to_json(), other.to_term())
+    }
+
+    fn set_difference<T: ToTerm>(self, other: T) -> SetDifference {
+        document::set_difference(self.to_json(), other.to_term())
+    }
+
+    fn insert_at<T: ToTerm>(self, index: int, value: T) -> InsertAt {
+        document::insert_at(self.to_json(), index, value.to_term())
+    }
+
+    fn delete_at(self, index: int) -> DeleteAt {
+        document::delete_at(self.to_json(), vec![index.to_json()])
+    }
+
+    /// Deletes the elements from `start` up to (but not including) `end`.
+    fn delete_at_range(self, start: int, end: int) -> DeleteAt {
+        document::delete_at(self.to_json(), vec![start.to_json(), end.to_json()])
+    }
+
+    /// Replaces the element at `index`.
+    fn change_at<T: ToTerm>(self, index: int, value: T) -> ChangeAt {
+        document::change_at(self.to_json(), index, value.to_term())
+    }
+
+    /// Inserts the elements of `values` at `index`.
+    fn splice_at<T: ToTerm>(self, index: int, values: T) -> SpliceAt {
+        document::splice_at(self.to_json(), index, values.to_term())
+    }
+
+    /// Sorts a sequence by the given keys, each of which can be a field name,
+    /// a function (see `r::func`), or either of those wrapped in `r::asc` or
+    /// `r::desc`. On a table, `.index(..)` orders by a secondary index (or the
+    /// primary key) before any other keys; that's much faster, and isn't
+    /// limited in the number of documents it can sort.
+    ///
+    /// The keys are trait objects so that the kinds can be mixed, e.g.
+    /// `order_by(&[&"name", &r::desc("age")])`.
+    fn order_by(self, keys: &[&ToTerm]) -> OrderBy {
+        sequence::order_by(self.to_json(), keys)
+    }
+
+    fn limit(self, n: uint) -> Limit {
+        sequence::limit(self.to_json(), n)
+    }
+
+    fn skip(self, n: uint) -> Skip {
+        sequence::skip(self.to_json(), n)
+    }
+
+    /// Takes the elements from `start` up to (but not including) `end`.
+    /// Negative indices count from the end of the sequence. Use
+    /// `Slice::left_bound` and `Slice::right_bound` to change which ends are
+    /// included.
+    fn slice(self, start: int, end: int) -> Slice {
+        sequence::slice(self.to_json(), start, end)
+    }
+
+    /// Gets the element at `index`, counting from the end if it's negative.
+    fn nth(self, index: int) -> Nth {
+        sequence::nth(self.to_json(), index)
+    }
+
+    /// Selects `n` elements of a sequence at random.
+    fn sample(self, n: uint) -> Sample {
+        sequence::sample(self.to_json(), n)
+    }
+
+    fn is_empty(self) -> IsEmpty {
+        sequence::is_empty(self.to_json())
+    }
+
+    /// Tests whether a sequence contains a value, or an element for which a
+    /// function (see `r::func`) returns true.
+    fn contains<T: ToTerm>(self, value: T) -> Contains {
+        sequence::contains(self.to_json(), value.to_term())
+    }
+
+    /// Finds the indices of the elements of a sequence which are equal to a
+    /// value, or for which a function returns true.
+    fn indexes_of<T: ToTerm>(self, value: T) -> IndexesOf {
+        sequence::indexes_of(self.to_json(), value.to_term())
+    }
+
+    /// Transforms each element of a sequence with a function.
+    fn map<R: ToTerm>(self, f: |Var| -> R) -> Map {
+        sequence::map(self.to_json(), func(f))
+    }
+
+    /// Like `map`, but the function returns a sequence for each element, and
+    /// the results are concatenated.
+    fn concat_map<R: ToTerm>(self, f: |Var| -> R) -> ConcatMap {
+        sequence::concat_map(self.to_json(), func(f))
+    }
+
+    /// Combines the elements of a sequence into a single value, two at a
+    /// time. The function should be associative: the server doesn't promise
+    /// any particular order of reduction.
+    fn reduce<R: ToTerm>(self, f: |Var, Var| -> R) -> Reduce {
+        sequence::reduce(self.to_json(), func2(f))
+    }
+
+    /// Keeps the elements of a sequence for which a function returns true.
+    fn filter<R: ToTerm>(self, f: |Var| -> R) -> Filter {
+        sequence::filter(self.to_json(), func(f))
+    }
+
+    /// Keeps the elements of a sequence which match every field of an
+    /// object, e.g. `json!({"age": 30})`.
+    fn filter_by<T: ToTerm>(self, predicate: T) -> Filter {
+        sequence::filter(self.to_json(), predicate.to_term())
+    }
+
+    /// Runs a write query (or an array of them) for each element of a
+    /// sequence, returning the combined results.
+    fn for_each<R: ToTerm>(self, f: |Var| -> R) -> ForEach {
+        sequence::for_each(self.to_json(), func(f))
+    }
+
+    fn count(self) -> Count {
+        aggregate::count(self.to_json(), vec![])
+    }
+
+    /// Counts the elements of a sequence which are equal to a value, or for
+    /// which a function (see `r::func`) returns true.
+    fn count_by<T: ToTerm>(self, x: T) -> Count {
+        aggregate::count(self.to_json(), vec![x.to_term()])
+    }
+
+    fn sum(self) -> Sum {
+        aggregate::sum(self.to_json(), vec![])
+    }
+
+    /// Sums a field of the elements of a sequence, or the results of calling
+    /// a function on them.
+    fn sum_by<T: ToTerm>(self, field: T) -> Sum {
+        aggregate::sum(self.to_json(), vec![field.to_term()])
+    }
+
+    fn avg(self) -> Avg {
+        aggregate::avg(self.to_json(), vec![])
+    }
+
+    /// Averages a field of the elements of a sequence, or the results of
+    /// calling a function on them.
+    fn avg_by<T: ToTerm>(self, field: T) -> Avg {
+        aggregate::avg(self.to_json(), vec![field.to_term()])
+    }
+
+    /// Finds the smallest element of a sequence. On a table, use `Min::index`
+    /// to find it using a secondary index instead.
+    fn min(self) -> Min {
+        aggregate::min(self.to_json(), vec![])
+    }
+
+    /// Finds the element of a sequence with the smallest value of a field,
+    /// or of a function called on each element.
+    fn min_by<T: ToTerm>(self, field: T) -> Min {
+        aggregate::min(self.to_json(), vec![field.to_term()])
+    }
+
+    /// Finds the largest element of a sequence. On a table, use `Max::index`
+    /// to find it using a secondary index instead.
+    fn max(self) -> Max {
+        aggregate::max(self.to_json(), vec![])
+    }
+
+    /// Finds the element of a sequence with the largest value of a field, or
+    /// of a function called on each element.
+    fn max_by<T: ToTerm>(self, field: T) -> Max {
+        aggregate::max(self.to_json(), vec![field.to_term()])
+    }
+
+    /// Groups the elements of a sequence by the values of fields, or the
+    /// results of functions. Aggregating the groups (e.g. with `count`)
+    /// works on each group separately; either way, the result can be
+    /// decoded into a `Grouped` or turned back into a sequence with
+    /// `ungroup`. On a table, use `Group::index` to group by a secondary
+    /// index instead.
+    fn group<K: ToTerm>(self, keys: &[K]) -> Group {
+        group::group(self.to_json(), keys)
+    }
+
+    /// Pairs up every element of this sequence with every element of
+    /// `other` for which the predicate returns true. The predicate is
+    /// called with an element from each side, left first.
+    fn inner_join<T: ToTerm, R: ToTerm>(self, other: T, f: |Var, Var| -> R) -> InnerJoin {
+        join::inner_join(self.to_json(), other.to_term(), func2(f))
+    }
+
+    /// Like `inner_join`, but elements of this sequence with no match in
+    /// `other` are kept, without a right-hand side.
+    fn outer_join<T: ToTerm, R: ToTerm>(self, other: T, f: |Var, Var| -> R) -> OuterJoin {
+        join::outer_join(self.to_json(), other.to_term(), func2(f))
+    }
+
+    /// Joins each element of this sequence with the document of the `right`
+    /// table whose primary key equals `left_field` (a field name or
+    /// function) of the element. Use `EqJoin::index` to match against a
+    /// secondary index instead of the primary key.
+    fn eq_join<K: ToTerm, T: ToTerm>(self, left_field: K, right: T) -> EqJoin {
+        join::eq_join(self.to_json(), left_field.to_term(), right.to_term())
+    }
+
+    /// Merges the left and right sides of the results of a join.
+    fn zip(self) -> Zip {
+        join::zip(self.to_json())
+    }
+
+    /// Removes duplicate elements from a sequence. On a table, use
+    /// `Distinct::index` to get the distinct values of a secondary index.
+    fn distinct(self) -> Distinct {
+        aggregate::distinct(self.to_json())
+    }
+
+    /// Matches a string against a regular expression, in RE2 syntax. Runs
+    /// as `Option<MatchResult>`, which is `None` if there was no match.
+    fn match_(self, regex: &str) -> Match {
+        string::match_(self.to_json(), regex)
+    }
+
+    /// Splits a string on `separator`, or on whitespace if there's none, at
+    /// most `max_splits` times.
+    fn split(self, separator: Option<&str>, max_splits: Option<uint>) -> Split {
+        string::split(self.to_json(), separator, max_splits)
+    }
+
+    fn upcase(self) -> Upcase {
+        string::upcase(self.to_json())
+    }
+
+    fn downcase(self) -> Downcase {
+        string::downcase(self.to_json())
+    }
+
+    fn to_iso8601(self) -> ToIso8601 {
+        time::to_iso8601(self.to_json())
+    }
+
+    fn to_epoch_time(self) -> ToEpochTime {
+        time::to_epoch_time(self.to_json())
+    }
+
+    /// The same time, in a different timezone, e.g. `"-08:00"`.
+    fn in_timezone(self, timezone: &str) -> InTimezone {
+        time::in_timezone(self.to_json(), timezone)
+    }
+
+    /// Tests whether a time falls within `[start, end)`. Use
+    /// `During::left_bound` and `During::right_bound` to change the bounds.
+    fn during<S: ToTerm, E: ToTerm>(self, start: S, end: E) -> During {
+        time::during(self.to_json(), start.to_term(), end.to_term())
+    }
+
+    /// Midnight at the start of a time's day.
+    fn date(self) -> Date {
+        time::date(self.to_json())
+    }
+
+    fn timezone(self) -> Timezone {
+        time::timezone(self.to_json())
+    }
+
+    /// The number of seconds since midnight.
+    fn time_of_day(self) -> TimeOfDay {
+        time::time_of_day(self.to_json())
+    }
+
+    fn year(self) -> Year {
+        time::year(self.to_json())
+    }
+
+    fn month(self) -> Month {
+        time::month(self.to_json())
+    }
+
+    fn day(self) -> Day {
+        time::day(self.to_json())
+    }
+
+    /// The ISO 8601 day of the week, from 1 (Monday) to 7.
+    fn day_of_week(self) -> DayOfWeek {
+        time::day_of_week(self.to_json())
+    }
+
+    fn day_of_year(self) -> DayOfYear {
+        time::day_of_year(self.to_json())
+    }
+
+    fn hours(self) -> Hours {
+        time::hours(self.to_json())
+    }
+
+    fn minutes(self) -> Minutes {
+        time::minutes(self.to_json())
+    }
+
+    fn seconds(self) -> Seconds {
+        time::seconds(self.to_json())
+    }
+
+    fn to_geojson(self) -> ToGeoJson {
+        geo::to_geojson(self.to_json())
+    }
+
+    /// The distance between two geometries, at least one of which is a
+    /// point. It's in meters, unless `Distance::unit` is given.
+    fn distance<T: ToTerm>(self, other: T) -> Distance {
+        geo::distance(self.to_json(), other.to_term())
+    }
+
+    /// Tests whether two geometries intersect. On a sequence of geometries,
+    /// filters it down to those which intersect `other`.
+    fn intersects<T: ToTerm>(self, other: T) -> Intersects {
+        geo::intersects(self.to_json(), other.to_term())
+    }
+
+    /// Tests whether a polygon completely contains `other`. On a sequence of
+    /// polygons, filters it down to those which do.
+    fn includes<T: ToTerm>(self, other: T) -> Includes {
+        geo::includes(self.to_json(), other.to_term())
+    }
+
+    /// Converts a closed line into a polygon.
+    fn fill(self) -> Fill {
+        geo::fill(self.to_json())
+    }
+
+    /// Cuts the polygon `inner` out of this polygon.
+    fn polygon_sub<T: ToTerm>(self, inner: T) -> PolygonSub {
+        geo::polygon_sub(self.to_json(), inner.to_term())
+    }
+
+    /// Watches a table, document or selection (e.g. filtered, or ordered by
+    /// an index and limited) for changes. Runs as a `ChangeFeed<T>`, an endless iterator of
+    /// `Change<T>`s.
+    fn changes(self) -> Changes {
+        changes::changes(self.to_json())
+    }
+
+    /// Evaluates to `value` instead if this expression is `null` or fails
+    /// with a non-existence error, e.g. a missing field. `value` may also be
+    /// a function (see `r::func`) of the error message.
+    fn default<T: ToTerm>(self, value: T) -> DefaultTerm {
+        control::default(self.to_json(), value.to_term())
+    }
+
+    /// Converts a value to another type, given its ReQL name, e.g. a
+    /// sequence of `[key, value]` pairs to an object with `"object"`.
+    fn coerce_to(self, type_name: &str) -> CoerceTo {
+        control::coerce_to(self.to_json(), type_name)
+    }
+
+    /// Gets the ReQL name of this value's type, e.g. `"NUMBER"`.
+    fn type_of(self) -> TypeOf {
+        control::type_of(self.to_json())
+    }
+
+    /// Gets information about a value, such as a table's name and indexes.
+    fn info(self) -> Info {
+        control::info(self.to_json())
     }
 }
 
 #[cfg(test)]
 mod test {
     use query::expr as r;
+    use query::Expr;
     use serialize::json::ToJson;
 
     #[test]
     fn test_expr() {
         assert_eq!((r::expr(1i32)).to_json(), json!(1));
         assert_eq!((r::expr(1f64)).to_json(), json!(1.0));
-        assert_eq!((r::expr(vec![1i32, 2, 3, 4])).to_json(), json!([1, 2, 3, 4]));
+        assert_eq!((r::expr(vec![1i32, 2, 3, 4])).to_json(), json!([2, [1, 2, 3, 4]]));
+    }
+
+    #[test]
+    fn test_expr_term() {
+        use query as q;
+
+        assert_eq!(r::expr(q::table("foo").get_field("a")).to_json(), json!([31, [[15, ["foo"]], "a"]]));
+        assert_eq!(r::expr(vec![q::table("foo"), q::table("bar")]).to_json(),
+                   json!([2, [[15, ["foo"]], [15, ["bar"]]]]));
+        assert_eq!(r::expr(r::expr(json!([1]))).to_json(), json!([2, [1]]));
+        assert_eq!(q::table("foo").insert(r::expr(json!({"a": [1]}))).to_json(),
+                   json!([56, [[15, ["foo"]], {"a": [2, [1]]}]]));
+    }
+
+    // Expected values are the wire format produced by the official Python
+    // driver for the expression in the comment above each assertion.
+    #[test]
+    fn test_datum() {
+        use query as q;
+
+        // r.expr([[1], []])
+        assert_eq!(r::expr(json!([[1], []])).to_json(), json!([2, [[2, [1]], [2, []]]]));
+        // r.expr({'a': [1, {'b': [2]}], 'c': {}})
+        assert_eq!(r::expr(json!({"a": [1, {"b": [2]}], "c": {}})).to_json(),
+                   json!({"a": [2, [1, {"b": [2, [2]]}]], "c": {}}));
+        // r.table('foo').insert({'tags': ['a', 'b']})
+        assert_eq!(q::table("foo").insert(json!({"tags": ["a", "b"]})).to_json(),
+                   json!([56, [[15, ["foo"]], {"tags": [2, ["a", "b"]]}]]));
+        // r.table('foo').insert([{'id': 1}, {'id': 2}])
+        assert_eq!(q::table("foo").insert(vec![json!({"id": 1}), json!({"id": 2})]).to_json(),
+                   json!([56, [[15, ["foo"]], [2, [{"id": 1}, {"id": 2}]]]]));
+        // r.table('foo').get([1, 'a'])
+        assert_eq!(q::table("foo").get(json!([1, "a"])).to_json(),
+                   json!([16, [[15, ["foo"]], [2, [1, "a"]]]]));
+        // r.table('foo').get('a').set_union(['b', 'c'])
+        assert_eq!(q::table("foo").get("a").set_union(vec!["b", "c"]).to_json(),
+                   json!([90, [[16, [[15, ["foo"]], "a"]], [2, ["b", "c"]]]]));
+        // r.table('foo').distinct(index=['a'])
+        assert_eq!(q::table("foo").distinct().index(vec!["a"]).to_json(),
+                   json!([42, [[15, ["foo"]]], {"index": [2, ["a"]]}]));
     }
 
     #[test]
     fn test_ops() {
         assert_eq!((r::expr(1i32) + 10i32).to_json(), json!([24, [1, 10]]));
+        assert_eq!((r::expr(1i32) - 10i32).to_json(), json!([25, [1, 10]]));
+        assert_eq!((r::expr(1i32) * 10i32).to_json(), json!([26, [1, 10]]));
+        assert_eq!((r::expr(1i32) / 10i32).to_json(), json!([27, [1, 10]]));
+        assert_eq!((r::expr(1i32) % 10i32).to_json(), json!([28, [1, 10]]));
+    }
+
+    #[test]
+    fn test_chained_ops() {
+        assert_eq!(((r::expr(1i32) + 2i32) * 3i32).to_json(), json!([26, [[24, [1, 2]], 3]]));
+        assert_eq!((r::expr(7i32).rem(2i32).sub(1i32)).to_json(), json!([25, [[28, [7, 2]], 1]]));
+        assert_eq!((r::expr(8i32).div(2i32).add(1i32)).to_json(), json!([24, [[27, [8, 2]], 1]]));
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert_eq!(r::expr(1i32).eq(2i32).to_json(), json!([17, [1, 2]]));
+        assert_eq!(r::expr(1i32).ne(2i32).to_json(), json!([18, [1, 2]]));
+        assert_eq!(r::expr(1i32).lt(2i32).to_json(), json!([19, [1, 2]]));
+        assert_eq!(r::expr(1i32).le(2i32).to_json(), json!([20, [1, 2]]));
+        assert_eq!(r::expr(1i32).gt(2i32).to_json(), json!([21, [1, 2]]));
+        assert_eq!(r::expr(1i32).ge(2i32).to_json(), json!([22, [1, 2]]));
+        assert_eq!(r::expr(true).not().to_json(), json!([23, [true]]));
+        assert_eq!(r::expr(1i32).lt(2i32).and(r::expr(2i32).lt(3i32)).to_json(),
+                   json!([67, [[19, [1, 2]], [19, [2, 3]]]]));
+        assert_eq!(r::expr(false).or(true).to_json(), json!([66, [false, true]]));
     }
 }
@@ -0,0 +1,124 @@
+use serialize::json::{mod, Json};
+
+use super::cursor::Cursor;
+use super::expr::datum;
+use super::term_type as ty;
+
+/// Selects fields of a document for `pluck`, `without`, `has_fields` and
+/// `with_fields`: either a field name, or a JSON object or array of nested
+/// selectors such as `{"address": ["city", "country"]}`.
+pub trait Pathspec {
+    fn to_pathspec(&self) -> Json;
+}
+
+impl<'a> Pathspec for &'a str {
+    fn to_pathspec(&self) -> Json {
+        json::String(self.to_string())
+    }
+}
+
+impl Pathspec for String {
+    fn to_pathspec(&self) -> Json {
+        json::String(self.clone())
+    }
+}
+
+impl Pathspec for Json {
+    fn to_pathspec(&self) -> Json {
+        datum(self)
+    }
+}
+
+fn pathspecs<P: Pathspec>(selectors: &[P]) -> Vec<Json> {
+    selectors.iter().map(|x| x.to_pathspec()).collect()
+}
+
+query! {
+    GetField -> Json {
+        obj: Json,
+        field: String
+    } ty::GET_FIELD
+}
+
+query! {
+    Bracket -> Json {
+        obj: Json,
+        attr: Json
+    } ty::BRACKET
+}
+
+// pluck, without and has_fields work on a single object as well as on a
+// sequence, where they map over (or filter, for has_fields) the documents. So
+// they can be run for either kind of result.
+
+query! {
+    Pluck -> Json {
+        obj: Json ; ..selectors
+    } ty::PLUCK
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Pluck {
+}
+
+query! {
+    Without -> Json {
+        obj: Json ; ..selectors
+    } ty::WITHOUT
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Without {
+}
+
+query! {
+    HasFields -> bool {
+        obj: Json ; ..selectors
+    } ty::HAS_FIELDS
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for HasFields {
+}
+
+term! {
+    WithFields {
+        seq: Json ; ..selectors
+    } ty::WITH_FIELDS
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for WithFields {
+}
+
+query! {
+    Keys -> Vec<String> {
+        obj: Json
+    } ty::KEYS
+}
+
+pub fn get_field(obj: Json, field: &str) -> GetField {
+    GetField { obj: obj, field: field.into_string() }
+}
+
+pub fn bracket(obj: Json, attr: Json) -> Bracket {
+    Bracket { obj: obj, attr: attr }
+}
+
+pub fn pluck<P: Pathspec>(obj: Json, selectors: &[P]) -> Pluck {
+    Pluck { obj: obj, selectors: pathspecs(selectors) }
+}
+
+pub fn without<P: Pathspec>(obj: Json, selectors: &[P]) -> Without {
+    Without { obj: obj, selectors: pathspecs(selectors) }
+}
+
+pub fn has_fields<P: Pathspec>(obj: Json, selectors: &[P]) -> HasFields {
+    HasFields { obj: obj, selectors: pathspecs(selectors) }
+}
+
+pub fn with_fields<P: Pathspec>(seq: Json, selectors: &[P]) -> WithFields {
+    WithFields { seq: seq, selectors: pathspecs(selectors) }
+}
+
+pub fn keys(obj: Json) -> Keys {
+    Keys { obj: obj }
+}
+
+impl_expr! { GetField, Bracket, Pluck, Without, HasFields, WithFields, Keys }
@@ -0,0 +1,85 @@
+use std::sync::atomic::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+
+use serialize::json::{Json, ToJson};
+
+use super::ToTerm;
+use super::term_type as ty;
+
+// Variables only need to be unique within the query they appear in, but a
+// global counter is the simplest way to keep nested functions from clashing.
+static NEXT_VAR_ID: AtomicUint = INIT_ATOMIC_UINT;
+
+term! {
+    Var {
+        id: uint
+    } ty::VAR
+}
+
+fn var() -> Var {
+    Var { id: NEXT_VAR_ID.fetch_add(1, SeqCst) + 1 }
+}
+
+term! {
+    Func {
+        params: Json,
+        body: Json
+    } ty::FUNC
+}
+
+impl ToTerm for Func {
+    fn to_term(&self) -> Json {
+        self.to_json()
+    }
+}
+
+/// Builds a ReQL function of one argument, e.g. for `map` or `order_by`. The
+/// closure is called once, with a variable standing in for the argument, to
+/// build the body of the function.
+///
+/// ```
+/// r::table("people").order_by(&[&r::func(|p| p.get_field("age"))])
+/// ```
+pub fn func<R: ToTerm>(f: |Var| -> R) -> Func {
+    let x = var();
+    let params = (ty::MAKE_ARRAY, vec![x.id]).to_json();
+    Func { params: params, body: f(x).to_term() }
+}
+
+/// Builds a ReQL function of two arguments, e.g. for `reduce`; see `func`.
+pub fn func2<R: ToTerm>(f: |Var, Var| -> R) -> Func {
+    let (x, y) = (var(), var());
+    let params = (ty::MAKE_ARRAY, vec![x.id, y.id]).to_json();
+    Func { params: params, body: f(x, y).to_term() }
+}
+
+impl_expr! { Var }
+
+#[cfg(test)]
+mod test {
+    use query::Expr;
+    use serialize::json::ToJson;
+
+    use super::{func, func2};
+
+    #[test]
+    fn test_func() {
+        let mut id = 0u;
+        let f = func(|x| {
+            id = x.id;
+            x.get_field("age")
+        });
+        assert_eq!(f.to_json(), json!([69, [[2, [(id)]], [31, [[10, [(id)]], "age"]]]]));
+    }
+
+    #[test]
+    fn test_func2() {
+        let mut ids = (0u, 0u);
+        let f = func2(|x, y| {
+            ids = (x.id, y.id);
+            x + y
+        });
+        let (x, y) = ids;
+        assert!(x != y);
+        assert_eq!(f.to_json(), json!([69, [[2, [(x), (y)]], [24, [[10, [(x)]], [10, [(y)]]]]]]));
+    }
+}
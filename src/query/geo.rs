@@ -0,0 +1,344 @@
+use std::collections::TreeMap;
+
+use serialize::{Decodable, Decoder};
+use serialize::json::{mod, Json, ToJson};
+
+use errors::RdbResult;
+use from_response::{mod, FromResponse};
+use net::{Connection, Response};
+
+use super::ToTerm;
+use super::cursor::Cursor;
+use super::expr::datum;
+use super::term_type as ty;
+
+/// A point on the earth's surface, as a value: it's sent and received as the
+/// `GEOMETRY` pseudo-type, so it can be stored in documents. Use `point` for
+/// ReQL's `point` term.
+#[deriving(Clone, Show, PartialEq)]
+pub struct Point {
+    pub longitude: f64,
+    pub latitude: f64
+}
+
+impl Point {
+    pub fn new(longitude: f64, latitude: f64) -> Point {
+        Point { longitude: longitude, latitude: latitude }
+    }
+
+    fn coordinates(&self) -> Json {
+        json::Array(vec![self.longitude.to_json(), self.latitude.to_json()])
+    }
+}
+
+impl ToJson for Point {
+    fn to_json(&self) -> Json {
+        pseudo_type("Point", self.coordinates())
+    }
+}
+
+impl ToTerm for Point {
+    fn to_term(&self) -> Json {
+        datum(&self.to_json())
+    }
+}
+
+/// A geometry object, as RethinkDB's `GEOMETRY` pseudo-type, which is how
+/// it's both sent and received. A polygon is a list of rings, the first its
+/// outline and the rest holes cut out of it. The `point`, `line` and `polygon`
+/// terms build geometry on the server instead.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Geometry {
+    Point(Point),
+    Line(Vec<Point>),
+    Polygon(Vec<Vec<Point>>)
+}
+
+impl ToJson for Geometry {
+    fn to_json(&self) -> Json {
+        match *self {
+            Geometry::Point(ref p) => p.to_json(),
+            Geometry::Line(ref points) => {
+                let coordinates = points.iter().map(|p| p.coordinates()).collect();
+                pseudo_type("LineString", json::Array(coordinates))
+            }
+            Geometry::Polygon(ref rings) => {
+                let coordinates = rings.iter().map(|ring| ring_coordinates(ring.as_slice())).collect();
+                pseudo_type("Polygon", json::Array(coordinates))
+            }
+        }
+    }
+}
+
+impl ToTerm for Geometry {
+    fn to_term(&self) -> Json {
+        datum(&self.to_json())
+    }
+}
+
+fn pseudo_type(geo_type: &str, coordinates: Json) -> Json {
+    let mut d = TreeMap::new();
+    d.insert("$reql_type$".to_string(), "GEOMETRY".to_string().to_json());
+    d.insert("type".to_string(), geo_type.to_string().to_json());
+    d.insert("coordinates".to_string(), coordinates);
+    json::Object(d)
+}
+
+// GeoJSON rings end by repeating their first point, which the server expects
+// of the pseudo-type too.
+fn ring_coordinates(ring: &[Point]) -> Json {
+    let mut coordinates: Vec<Json> = ring.iter().map(|p| p.coordinates()).collect();
+    match (ring.head(), ring.last()) {
+        (Some(first), Some(last)) if first != last => coordinates.push(first.coordinates()),
+        _ => { }
+    }
+    json::Array(coordinates)
+}
+
+impl<E, D: Decoder<E>> Decodable<D, E> for Geometry {
+    fn decode(d: &mut D) -> Result<Geometry, E> {
+        fn to_point((longitude, latitude): (f64, f64)) -> Point {
+            Point::new(longitude, latitude)
+        }
+
+        d.read_struct("Geometry", 3, |d| {
+            let reql_type: String = try!(d.read_struct_field("$reql_type$", 0, |d| Decodable::decode(d)));
+            if reql_type.as_slice() != "GEOMETRY" {
+                return Err(d.error(format!("expected a GEOMETRY, got {}", reql_type).as_slice()));
+            }
+            let geo_type: String = try!(d.read_struct_field("type", 1, |d| Decodable::decode(d)));
+            match geo_type.as_slice() {
+                "Point" => {
+                    let coords: (f64, f64) =
+                        try!(d.read_struct_field("coordinates", 2, |d| Decodable::decode(d)));
+                    Ok(Geometry::Point(to_point(coords)))
+                }
+                "LineString" => {
+                    let coords: Vec<(f64, f64)> =
+                        try!(d.read_struct_field("coordinates", 2, |d| Decodable::decode(d)));
+                    Ok(Geometry::Line(coords.into_iter().map(|c| to_point(c)).collect()))
+                }
+                "Polygon" => {
+                    let coords: Vec<Vec<(f64, f64)>> =
+                        try!(d.read_struct_field("coordinates", 2, |d| Decodable::decode(d)));
+                    Ok(Geometry::Polygon(coords.into_iter().map(|ring| {
+                        ring.into_iter().map(|c| to_point(c)).collect()
+                    }).collect()))
+                }
+                _ => Err(d.error(format!("unknown geometry type {}", geo_type).as_slice()))
+            }
+        })
+    }
+}
+
+impl<'a> FromResponse<'a> for Geometry {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Geometry> {
+        from_response::decode(try!(from_response::atom(res)))
+    }
+}
+
+/// A result of `get_nearest`: a document and its distance from the point.
+#[deriving(Decodable, Show)]
+pub struct Nearest<T> {
+    pub dist: f64,
+    pub doc: T
+}
+
+query! {
+    PointTerm -> Geometry {
+        longitude: f64,
+        latitude: f64
+    } ty::POINT
+}
+
+pub fn point(longitude: f64, latitude: f64) -> PointTerm {
+    PointTerm { longitude: longitude, latitude: latitude }
+}
+
+query! {
+    Line -> Geometry {
+        ; ..points
+    } ty::LINE
+}
+
+/// A line through the given points, which may be `point` terms or `Point`
+/// values.
+pub fn line<T: ToTerm>(points: &[T]) -> Line {
+    Line { points: points.iter().map(|p| p.to_term()).collect() }
+}
+
+query! {
+    Polygon -> Geometry {
+        ; ..points
+    } ty::POLYGON
+}
+
+/// A polygon with the given vertices, which needn't repeat the first point at
+/// the end. Use `polygon_sub` to cut holes out of it.
+pub fn polygon<T: ToTerm>(points: &[T]) -> Polygon {
+    Polygon { points: points.iter().map(|p| p.to_term()).collect() }
+}
+
+query! {
+    Circle -> Geometry {
+        center: Json,
+        radius: f64
+    } opt_args { num_vertices, geo_system, unit, fill } ty::CIRCLE
+}
+
+/// A polygon approximating a circle of `radius` (in meters, unless `unit` is
+/// given) around `center`. With `fill(false)`, the circle is a line instead.
+pub fn circle<T: ToTerm>(center: T, radius: f64) -> Circle {
+    Circle { center: center.to_term(), radius: radius, opt_args: TreeMap::new() }
+}
+
+query! {
+    GeoJson -> Geometry {
+        geojson: Json
+    } ty::GEOJSON
+}
+
+/// Converts a GeoJSON object to a geometry.
+pub fn geojson(geojson: Json) -> GeoJson {
+    GeoJson { geojson: geojson.to_term() }
+}
+
+query! {
+    ToGeoJson -> Json {
+        geometry: Json
+    } ty::TO_GEOJSON
+}
+
+pub fn to_geojson(geometry: Json) -> ToGeoJson {
+    ToGeoJson { geometry: geometry }
+}
+
+query! {
+    Distance -> f64 {
+        a: Json,
+        b: Json
+    } opt_args { geo_system, unit } ty::DISTANCE
+}
+
+pub fn distance(a: Json, b: Json) -> Distance {
+    Distance { a: a, b: b, opt_args: TreeMap::new() }
+}
+
+query! {
+    Intersects -> bool {
+        a: Json,
+        b: Json
+    } ty::INTERSECTS
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Intersects {
+}
+
+pub fn intersects(a: Json, b: Json) -> Intersects {
+    Intersects { a: a, b: b }
+}
+
+query! {
+    Includes -> bool {
+        a: Json,
+        b: Json
+    } ty::INCLUDES
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Includes {
+}
+
+pub fn includes(a: Json, b: Json) -> Includes {
+    Includes { a: a, b: b }
+}
+
+query! {
+    Fill -> Geometry {
+        line: Json
+    } ty::FILL
+}
+
+pub fn fill(line: Json) -> Fill {
+    Fill { line: line }
+}
+
+query! {
+    PolygonSub -> Geometry {
+        outer: Json,
+        inner: Json
+    } ty::POLYGON_SUB
+}
+
+pub fn polygon_sub(outer: Json, inner: Json) -> PolygonSub {
+    PolygonSub { outer: outer, inner: inner }
+}
+
+term! {
+    GetIntersecting {
+        table: Json,
+        geometry: Json
+    } opt_args { index } ty::GET_INTERSECTING
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for GetIntersecting {
+}
+
+pub fn get_intersecting(table: Json, geometry: Json, index: &str) -> GetIntersecting {
+    GetIntersecting { table: table, geometry: geometry, opt_args: TreeMap::new() }.index(index)
+}
+
+term! {
+    GetNearest {
+        table: Json,
+        point: Json
+    } opt_args { index, max_results, max_dist, unit, geo_system } ty::GET_NEAREST
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for GetNearest {
+}
+
+pub fn get_nearest(table: Json, point: Json, index: &str) -> GetNearest {
+    GetNearest { table: table, point: point, opt_args: TreeMap::new() }.index(index)
+}
+
+impl_expr! {
+    PointTerm, Line, Polygon, Circle, GeoJson, ToGeoJson, Distance, Intersects,
+    Includes, Fill, PolygonSub, GetIntersecting, GetNearest
+}
+
+#[cfg(test)]
+mod test {
+    use from_response;
+    use serialize::json::{mod, ToJson};
+
+    use super::{Geometry, Point};
+
+    #[test]
+    fn test_geometry_round_trip() {
+        let datum = json::from_str(r#"{"$reql_type$": "GEOMETRY", "type": "Point",
+                                       "coordinates": [-122.423246, 37.779388]}"#).unwrap();
+        let geometry: Geometry = from_response::decode(datum.clone()).unwrap();
+        assert_eq!(geometry, Geometry::Point(Point::new(-122.423246, 37.779388)));
+        assert_eq!(geometry.to_json(), datum);
+
+        let datum = json::from_str(r#"{"$reql_type$": "GEOMETRY", "type": "LineString",
+                                       "coordinates": [[0.0, 0.0], [1.0, 1.0]]}"#).unwrap();
+        let geometry: Geometry = from_response::decode(datum.clone()).unwrap();
+        assert_eq!(geometry, Geometry::Line(vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0)]));
+        assert_eq!(geometry.to_json(), datum);
+
+        let datum = json::from_str(r#"{"$reql_type$": "GEOMETRY", "type": "Polygon",
+                                       "coordinates": [[[0.0, 0.0], [0.0, 4.0], [4.0, 4.0], [0.0, 0.0]],
+                                                       [[1.0, 1.0], [1.0, 2.0], [2.0, 2.0], [1.0, 1.0]]]}"#).unwrap();
+        let geometry: Geometry = from_response::decode(datum.clone()).unwrap();
+        assert_eq!(geometry.to_json(), datum);
+
+        let open = Geometry::Polygon(vec![vec![Point::new(0.0, 0.0), Point::new(0.0, 4.0), Point::new(4.0, 4.0)],
+                                          vec![Point::new(1.0, 1.0), Point::new(1.0, 2.0), Point::new(2.0, 2.0)]]);
+        assert_eq!(open.to_json(), datum);
+
+        let datum = json::from_str(r#"{"$reql_type$": "GEOMETRY", "type": "MultiPoint",
+                                       "coordinates": [[0, 0]]}"#).unwrap();
+        assert!(from_response::decode::<Geometry>(datum).is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::TreeMap;
+use std::slice;
+
+use serialize::Decodable;
+use serialize::json::{mod, Json, JsonObject, ToJson};
+
+use errors::RdbResult;
+use from_response::{mod, FromResponse};
+use net::{Connection, Response};
+
+use super::{Expr, Query, Term, ToTerm};
+use super::aggregate::{mod, Count, Sum, Avg, Min, Max};
+use super::cursor::Cursor;
+use super::func::{Var, func, func2};
+use super::sequence::{mod, Map, Reduce, Filter, OrderBy};
+use super::term_type as ty;
+
+term! {
+    Group {
+        seq: Json ; ..keys
+    } opt_args { index, multi } ty::GROUP
+}
+
+impl<'a, K, V> Query<'a, Grouped<K, V>> for Group
+    where K: Decodable<json::Decoder, json::DecoderError>,
+          V: Decodable<json::Decoder, json::DecoderError> {
+}
+
+pub fn group<K: ToTerm>(seq: Json, keys: &[K]) -> Group {
+    Group {
+        seq: seq,
+        keys: keys.iter().map(|x| x.to_term()).collect(),
+        opt_args: TreeMap::new()
+    }
+}
+
+// These shadow the Expr methods of the same name, because on grouped data they
+// operate on each group separately, and run to a Grouped result. They're the
+// same as those of GroupedQuery, so that grouped terms can be chained.
+impl Group {
+    fn grouped(self) -> GroupedQuery<Group> {
+        GroupedQuery { term: self }
+    }
+
+    pub fn count(self) -> GroupedQuery<Count> {
+        self.grouped().count()
+    }
+
+    pub fn count_by<T: ToTerm>(self, x: T) -> GroupedQuery<Count> {
+        self.grouped().count_by(x)
+    }
+
+    pub fn sum(self) -> GroupedQuery<Sum> {
+        self.grouped().sum()
+    }
+
+    pub fn sum_by<T: ToTerm>(self, field: T) -> GroupedQuery<Sum> {
+        self.grouped().sum_by(field)
+    }
+
+    pub fn avg(self) -> GroupedQuery<Avg> {
+        self.grouped().avg()
+    }
+
+    pub fn avg_by<T: ToTerm>(self, field: T) -> GroupedQuery<Avg> {
+        self.grouped().avg_by(field)
+    }
+
+    pub fn min(self) -> GroupedQuery<Min> {
+        self.grouped().min()
+    }
+
+    pub fn min_by<T: ToTerm>(self, field: T) -> GroupedQuery<Min> {
+        self.grouped().min_by(field)
+    }
+
+    pub fn max(self) -> GroupedQuery<Max> {
+        self.grouped().max()
+    }
+
+    pub fn max_by<T: ToTerm>(self, field: T) -> GroupedQuery<Max> {
+        self.grouped().max_by(field)
+    }
+
+    pub fn map<R: ToTerm>(self, f: |Var| -> R) -> GroupedQuery<Map> {
+        self.grouped().map(f)
+    }
+
+    pub fn reduce<R: ToTerm>(self, f: |Var, Var| -> R) -> GroupedQuery<Reduce> {
+        self.grouped().reduce(f)
+    }
+
+    pub fn filter<R: ToTerm>(self, f: |Var| -> R) -> GroupedQuery<Filter> {
+        self.grouped().filter(f)
+    }
+
+    pub fn filter_by<T: ToTerm>(self, predicate: T) -> GroupedQuery<Filter> {
+        self.grouped().filter_by(predicate)
+    }
+
+    pub fn order_by(self, keys: &[&ToTerm]) -> GroupedQuery<OrderBy> {
+        self.grouped().order_by(keys)
+    }
+
+    /// Turns the groups into an array of `{"group": ..., "reduction": ...}`
+    /// objects, so that they can be treated like any other sequence.
+    pub fn ungroup(self) -> Ungroup {
+        Ungroup { grouped: self.to_json() }
+    }
+}
+
+/// A term which operates on each group of a grouped query, and runs to a
+/// `Grouped` result rather than its usual one.
+pub struct GroupedQuery<T> {
+    term: T
+}
+
+impl<T: Term> Term for GroupedQuery<T> {
+    fn args(&self) -> Vec<Json> {
+        self.term.args()
+    }
+
+    fn opt_args(&self) -> Option<JsonObject> {
+        self.term.opt_args()
+    }
+}
+
+impl<T: ToJson> ToJson for GroupedQuery<T> {
+    fn to_json(&self) -> Json {
+        self.term.to_json()
+    }
+}
+
+impl<T: ToJson> ToTerm for GroupedQuery<T> {
+    fn to_term(&self) -> Json {
+        self.term.to_json()
+    }
+}
+
+impl<'a, T, K, V> Query<'a, Grouped<K, V>> for GroupedQuery<T>
+    where T: Term + ToJson,
+          K: Decodable<json::Decoder, json::DecoderError>,
+          V: Decodable<json::Decoder, json::DecoderError> {
+}
+
+// The terms which keep operating on each group, so that e.g. a grouped map can
+// be followed by an aggregation.
+impl<T: ToJson> GroupedQuery<T> {
+    pub fn count(self) -> GroupedQuery<Count> {
+        GroupedQuery { term: aggregate::count(self.to_json(), vec![]) }
+    }
+
+    pub fn count_by<U: ToTerm>(self, x: U) -> GroupedQuery<Count> {
+        GroupedQuery { term: aggregate::count(self.to_json(), vec![x.to_term()]) }
+    }
+
+    pub fn sum(self) -> GroupedQuery<Sum> {
+        GroupedQuery { term: aggregate::sum(self.to_json(), vec![]) }
+    }
+
+    pub fn sum_by<U: ToTerm>(self, field: U) -> GroupedQuery<Sum> {
+        GroupedQuery { term: aggregate::sum(self.to_json(), vec![field.to_term()]) }
+    }
+
+    pub fn avg(self) -> GroupedQuery<Avg> {
+        GroupedQuery { term: aggregate::avg(self.to_json(), vec![]) }
+    }
+
+    pub fn avg_by<U: ToTerm>(self, field: U) -> GroupedQuery<Avg> {
+        GroupedQuery { term: aggregate::avg(self.to_json(), vec![field.to_term()]) }
+    }
+
+    pub fn min(self) -> GroupedQuery<Min> {
+        GroupedQuery { term: aggregate::min(self.to_json(), vec![]) }
+    }
+
+    pub fn min_by<U: ToTerm>(self, field: U) -> GroupedQuery<Min> {
+        GroupedQuery { term: aggregate::min(self.to_json(), vec![field.to_term()]) }
+    }
+
+    pub fn max(self) -> GroupedQuery<Max> {
+        GroupedQuery { term: aggregate::max(self.to_json(), vec![]) }
+    }
+
+    pub fn max_by<U: ToTerm>(self, field: U) -> GroupedQuery<Max> {
+        GroupedQuery { term: aggregate::max(self.to_json(), vec![field.to_term()]) }
+    }
+
+    pub fn map<R: ToTerm>(self, f: |Var| -> R) -> GroupedQuery<Map> {
+        GroupedQuery { term: sequence::map(self.to_json(), func(f)) }
+    }
+
+    pub fn reduce<R: ToTerm>(self, f: |Var, Var| -> R) -> GroupedQuery<Reduce> {
+        GroupedQuery { term: sequence::reduce(self.to_json(), func2(f)) }
+    }
+
+    pub fn filter<R: ToTerm>(self, f: |Var| -> R) -> GroupedQuery<Filter> {
+        GroupedQuery { term: sequence::filter(self.to_json(), func(f)) }
+    }
+
+    pub fn filter_by<U: ToTerm>(self, predicate: U) -> GroupedQuery<Filter> {
+        GroupedQuery { term: sequence::filter(self.to_json(), predicate.to_term()) }
+    }
+
+    pub fn order_by(self, keys: &[&ToTerm]) -> GroupedQuery<OrderBy> {
+        GroupedQuery { term: sequence::order_by(self.to_json(), keys) }
+    }
+
+    /// See `Group::ungroup`.
+    pub fn ungroup(self) -> Ungroup {
+        Ungroup { grouped: self.term.to_json() }
+    }
+}
+
+// Expr treats the grouped data as a whole, e.g. `coerce_to("object")`.
+impl<T: ToJson> Expr for GroupedQuery<T> {
+}
+
+term! {
+    Ungroup {
+        grouped: Json
+    } ty::UNGROUP
+}
+
+impl<'a> Query<'a, Cursor<'a>> for Ungroup {
+}
+
+impl_expr! { Group, Ungroup }
+
+/// The result of a grouped query: the key of each group, paired with the
+/// group's documents or the result of aggregating them.
+#[deriving(Show)]
+pub struct Grouped<K, V> {
+    groups: Vec<(K, V)>
+}
+
+impl<K, V> Grouped<K, V> {
+    pub fn len(&self) -> uint {
+        self.groups.len()
+    }
+
+    pub fn iter(&self) -> slice::Items<(K, V)> {
+        self.groups.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<(K, V)> {
+        self.groups
+    }
+}
+
+impl<K: PartialEq, V> Grouped<K, V> {
+    /// Looks up the value for a group by its key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        for &(ref k, ref v) in self.groups.iter() {
+            if k == key {
+                return Some(v);
+            }
+        }
+        None
+    }
+}
+
+impl<K, V> Grouped<K, V>
+    where K: Decodable<json::Decoder, json::DecoderError>,
+          V: Decodable<json::Decoder, json::DecoderError> {
+    fn from_json(datum: Json) -> RdbResult<Grouped<K, V>> {
+        use errors::Error::DriverError;
+
+        match datum.find("$reql_type$").and_then(|x| x.as_string()) {
+            Some("GROUPED_DATA") => { },
+            _ => return Err(DriverError(format!("expected GROUPED_DATA, got {}", datum)))
+        }
+        let data = match datum.find("data").and_then(|x| x.as_array()) {
+            Some(data) => data,
+            None => return Err(DriverError(format!("couldn't find data in {}", datum)))
+        };
+
+        let mut groups = Vec::with_capacity(data.len());
+        for pair in data.iter() {
+            match pair.as_array().map(|x| x.as_slice()) {
+                Some([ref k, ref v]) => {
+                    let k = try!(from_response::decode(k.clone()));
+                    let v = try!(from_response::decode(v.clone()));
+                    groups.push((k, v));
+                }
+                _ => return Err(DriverError(format!("expected a [key, value] pair, got {}", pair)))
+            }
+        }
+        Ok(Grouped { groups: groups })
+    }
+}
+
+impl<'a, K, V> FromResponse<'a> for Grouped<K, V>
+    where K: Decodable<json::Decoder, json::DecoderError>,
+          V: Decodable<json::Decoder, json::DecoderError> {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Grouped<K, V>> {
+        Grouped::from_json(try!(from_response::atom(res)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serialize::json;
+
+    use errors::RdbResult;
+    use super::Grouped;
+
+    #[test]
+    fn test_grouped_from_json() {
+        let datum = json::from_str(r#"{"$reql_type$": "GROUPED_DATA",
+                                       "data": [["bob", 2], ["sally", 3]]}"#).unwrap();
+        let grouped: Grouped<String, u64> = Grouped::from_json(datum).unwrap();
+
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped.get(&"bob".to_string()), Some(&2));
+        assert_eq!(grouped.get(&"sally".to_string()), Some(&3));
+        assert_eq!(grouped.get(&"jay".to_string()), None);
+    }
+
+    #[test]
+    fn test_grouped_from_json_not_grouped() {
+        let datum = json::from_str(r#"[["bob", 2]]"#).unwrap();
+        let grouped: RdbResult<Grouped<String, u64>> = Grouped::from_json(datum);
+        assert!(grouped.is_err());
+    }
+}
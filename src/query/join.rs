@@ -0,0 +1,105 @@
+use std::collections::TreeMap;
+
+use serialize::json::Json;
+
+use super::cursor::Cursor;
+use super::func::Func;
+use super::term_type as ty;
+
+term! {
+    InnerJoin {
+        left: Json,
+        right: Json,
+        predicate: Func
+    } ty::INNER_JOIN
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for InnerJoin {
+}
+
+pub fn inner_join(left: Json, right: Json, predicate: Func) -> InnerJoin {
+    InnerJoin { left: left, right: right, predicate: predicate }
+}
+
+term! {
+    OuterJoin {
+        left: Json,
+        right: Json,
+        predicate: Func
+    } ty::OUTER_JOIN
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for OuterJoin {
+}
+
+pub fn outer_join(left: Json, right: Json, predicate: Func) -> OuterJoin {
+    OuterJoin { left: left, right: right, predicate: predicate }
+}
+
+term! {
+    EqJoin {
+        left: Json,
+        left_field: Json,
+        right: Json
+    } opt_args { index } ty::EQ_JOIN
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for EqJoin {
+}
+
+pub fn eq_join(left: Json, left_field: Json, right: Json) -> EqJoin {
+    EqJoin { left: left, left_field: left_field, right: right, opt_args: TreeMap::new() }
+}
+
+term! {
+    Zip {
+        seq: Json
+    } ty::ZIP
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Zip {
+}
+
+pub fn zip(seq: Json) -> Zip {
+    Zip { seq: seq }
+}
+
+impl_expr! { InnerJoin, OuterJoin, EqJoin, Zip }
+
+/// One result of a join: a document from the left-hand sequence, and the
+/// matching document from the right. For an outer join, make `R` an `Option`
+/// to allow for left-hand documents without a match.
+///
+/// ```
+/// let pairs: Vec<Joined<Employee, Department>> = try!(try!(query.run(conn)).decode());
+/// ```
+#[deriving(Decodable, Show)]
+pub struct Joined<L, R> {
+    pub left: L,
+    pub right: R
+}
+
+impl<L, R> Joined<L, R> {
+    pub fn into_pair(self) -> (L, R) {
+        (self.left, self.right)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use from_response;
+    use serialize::json;
+
+    use super::Joined;
+
+    #[test]
+    fn test_joined_decode() {
+        let datum = json::from_str(r#"{"left": "bob", "right": 23}"#).unwrap();
+        let joined: Joined<String, u64> = from_response::decode(datum).unwrap();
+        assert_eq!(joined.into_pair(), ("bob".to_string(), 23));
+
+        let datum = json::from_str(r#"{"left": "sally"}"#).unwrap();
+        let joined: Joined<String, Option<u64>> = from_response::decode(datum).unwrap();
+        assert_eq!(joined.into_pair(), ("sally".to_string(), None));
+    }
+}
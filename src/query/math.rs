@@ -0,0 +1,167 @@
+use serialize::json::Json;
+
+use super::term_type as ty;
+
+query! {
+    Add -> f64 { // NOTE: it's Number -> Number or String -> String, see below
+        lhs: Json,
+        rhs: Json
+    } ty::ADD
+}
+
+// Adding strings concatenates them, so Add can be run for either result type.
+// Callers have to annotate which one they want.
+impl<'a> ::query::Query<'a, String> for Add {
+}
+
+query! {
+    Sub -> f64 {
+        lhs: Json,
+        rhs: Json
+    } ty::SUB
+}
+
+query! {
+    Mul -> f64 {
+        lhs: Json,
+        rhs: Json
+    } ty::MUL
+}
+
+query! {
+    Div -> f64 {
+        lhs: Json,
+        rhs: Json
+    } ty::DIV
+}
+
+query! {
+    Mod -> f64 {
+        lhs: Json,
+        rhs: Json
+    } ty::MOD
+}
+
+pub fn add(lhs: Json, rhs: Json) -> Add {
+    Add { lhs: lhs, rhs: rhs }
+}
+
+pub fn sub(lhs: Json, rhs: Json) -> Sub {
+    Sub { lhs: lhs, rhs: rhs }
+}
+
+pub fn mul(lhs: Json, rhs: Json) -> Mul {
+    Mul { lhs: lhs, rhs: rhs }
+}
+
+pub fn div(lhs: Json, rhs: Json) -> Div {
+    Div { lhs: lhs, rhs: rhs }
+}
+
+pub fn rem(lhs: Json, rhs: Json) -> Mod {
+    Mod { lhs: lhs, rhs: rhs }
+}
+
+// Comparisons and boolean logic, mostly for the predicates of filter, joins
+// and branch. `EqTerm` and `NotTerm` are named so as not to shadow the std
+// traits.
+
+query! {
+    EqTerm -> bool {
+        lhs: Json,
+        rhs: Json
+    } ty::EQ
+}
+
+query! {
+    Ne -> bool {
+        lhs: Json,
+        rhs: Json
+    } ty::NE
+}
+
+query! {
+    Lt -> bool {
+        lhs: Json,
+        rhs: Json
+    } ty::LT
+}
+
+query! {
+    Le -> bool {
+        lhs: Json,
+        rhs: Json
+    } ty::LE
+}
+
+query! {
+    Gt -> bool {
+        lhs: Json,
+        rhs: Json
+    } ty::GT
+}
+
+query! {
+    Ge -> bool {
+        lhs: Json,
+        rhs: Json
+    } ty::GE
+}
+
+query! {
+    NotTerm -> bool {
+        value: Json
+    } ty::NOT
+}
+
+query! {
+    And -> bool {
+        lhs: Json,
+        rhs: Json
+    } ty::ALL
+}
+
+query! {
+    Or -> bool {
+        lhs: Json,
+        rhs: Json
+    } ty::ANY
+}
+
+pub fn eq(lhs: Json, rhs: Json) -> EqTerm {
+    EqTerm { lhs: lhs, rhs: rhs }
+}
+
+pub fn ne(lhs: Json, rhs: Json) -> Ne {
+    Ne { lhs: lhs, rhs: rhs }
+}
+
+pub fn lt(lhs: Json, rhs: Json) -> Lt {
+    Lt { lhs: lhs, rhs: rhs }
+}
+
+pub fn le(lhs: Json, rhs: Json) -> Le {
+    Le { lhs: lhs, rhs: rhs }
+}
+
+pub fn gt(lhs: Json, rhs: Json) -> Gt {
+    Gt { lhs: lhs, rhs: rhs }
+}
+
+pub fn ge(lhs: Json, rhs: Json) -> Ge {
+    Ge { lhs: lhs, rhs: rhs }
+}
+
+pub fn not(value: Json) -> NotTerm {
+    NotTerm { value: value }
+}
+
+pub fn and(lhs: Json, rhs: Json) -> And {
+    And { lhs: lhs, rhs: rhs }
+}
+
+pub fn or(lhs: Json, rhs: Json) -> Or {
+    Or { lhs: lhs, rhs: rhs }
+}
+
+impl_expr! { Add, Sub, Mul, Div, Mod, EqTerm, Ne, Lt, Le, Gt, Ge, NotTerm, And, Or }
@@ -4,9 +4,37 @@ use from_response::FromResponse;
 use net;
 use RdbResult;
 
+pub use self::aggregate::{Count, Sum, Avg, Min, Max, Distinct};
+pub use self::binary::{Binary, BinaryFormat, BinaryTerm, binary};
+pub use self::changes::{Changes, Change, ChangeFeed};
+pub use self::control::{Branch, ErrorTerm, DefaultTerm, CoerceTo, TypeOf, Info, branch, error};
+pub use self::ddl::{Created, Dropped, Renamed};
 pub use self::db::{Db, DbCreate, DbDrop, DbList, db, db_create, db_drop, db_list};
-pub use self::expr::{Datum, expr};
-pub use self::table::{Table, TableCreate, TableDrop, TableList, Get};
+pub use self::expr::{Datum, Expr, ToTerm, expr};
+pub use self::document::{Merge, Append, Prepend, Difference};
+pub use self::document::{SetInsert, SetIntersection, SetUnion, SetDifference};
+pub use self::document::{InsertAt, DeleteAt, ChangeAt, SpliceAt};
+pub use self::document::{Literal, MakeObj, MakeArray, literal, empty_literal, make_obj, make_array};
+pub use self::fields::{GetField, Bracket, Pluck, Without, HasFields, WithFields, Keys};
+pub use self::fields::Pathspec;
+pub use self::geo::{Point, Geometry, Nearest, PointTerm, Line, Polygon, Circle, GeoJson, ToGeoJson};
+pub use self::geo::{Distance, Intersects, Includes, Fill, PolygonSub, GetIntersecting, GetNearest};
+pub use self::geo::{point, line, polygon, circle, geojson};
+pub use self::func::{Func, Var, func, func2};
+pub use self::group::{Group, GroupedQuery, Grouped, Ungroup};
+pub use self::join::{InnerJoin, OuterJoin, EqJoin, Zip, Joined};
+pub use self::math::{Add, Sub, Mul, Div, Mod, EqTerm, Ne, Lt, Le, Gt, Ge, NotTerm, And, Or};
+pub use self::sequence::{Order, OrderBy, asc, desc};
+pub use self::sequence::{Bound, Limit, Skip, Slice, Nth, Sample, IsEmpty};
+pub use self::sequence::{Map, ConcatMap, Reduce, ForEach, Filter, Contains, IndexesOf};
+pub use self::subscription::Subscription;
+pub use self::string::{Match, MatchResult, MatchGroup, Split, Upcase, Downcase};
+pub use self::time::{Time, TimeFormat, Now, TimeTerm, EpochTime, Iso8601, ToIso8601, ToEpochTime};
+pub use self::time::{InTimezone, During, Date, Timezone, TimeOfDay, Year, Month, Day};
+pub use self::time::{DayOfWeek, DayOfYear, Hours, Minutes, Seconds};
+pub use self::time::{now, time, time_hms, epoch_time, iso8601};
+pub use self::table::{Table, TableCreate, TableDrop, TableList, Get, Update, Delete};
+pub use self::table::{IndexCreate, IndexStatus, IndexStatusTerm, IndexWait};
 pub use self::table::{table, table_create, table_drop, table_list};
 
 #[doc(hidden)]
@@ -58,7 +86,86 @@ macro_rules! to_json_impl {
     }
 }
 
+// A builder method for each named optional argument of a term, e.g.
+// r::table("foo").order_by(&[&"bar"]).index("baz")
+macro_rules! opt_args_builders {
+    ($name:ident $($opt:ident),+) => {
+        impl $name {
+            $(
+                pub fn $opt<T: ::query::ToTerm>(mut self, value: T) -> $name {
+                    use query::ToTerm;
+                    self.opt_args.insert(stringify!($opt).into_string(), value.to_term());
+                    self
+                }
+            )+
+        }
+    };
+    // Enums keep their optional arguments in each variant, and provide an
+    // opt_args_mut method to get at them.
+    (enum $name:ident $($opt:ident),+) => {
+        impl $name {
+            $(
+                pub fn $opt<T: ::query::ToTerm>(mut self, value: T) -> $name {
+                    use query::ToTerm;
+                    self.opt_args_mut().insert(stringify!($opt).into_string(), value.to_term());
+                    self
+                }
+            )+
+        }
+    }
+}
+
 macro_rules! term {
+    // Terms with optional arguments. These have to come first, because the
+    // other rules would try (and fail) to parse `opt_args {...}` as the
+    // term type.
+    ($name:ident {
+        $($field:ident: $ty:ty),*
+    } opt_args { $($opt:ident),+ } $term_ty:expr) => {
+        pub struct $name {
+            $($field: $ty,)*
+            opt_args: ::serialize::json::JsonObject
+        }
+
+        impl ::query::Term for $name {
+            fn args(&self) -> Vec<::serialize::json::Json> {
+                use serialize::json::ToJson;
+                vec![$(self.$field.to_json()),*]
+            }
+
+            fn opt_args(&self) -> Option<::serialize::json::JsonObject> {
+                if self.opt_args.is_empty() { None } else { Some(self.opt_args.clone()) }
+            }
+        }
+
+        opt_args_builders! { $name $($opt),+ }
+        to_json_impl! { $name $term_ty }
+    };
+    ($name:ident {
+        $($field:ident: $ty:ty),* ; ..$rest:ident
+    } opt_args { $($opt:ident),+ } $term_ty:expr) => {
+        pub struct $name {
+            $($field: $ty,)*
+            $rest: Vec<::serialize::json::Json>,
+            opt_args: ::serialize::json::JsonObject
+        }
+
+        impl ::query::Term for $name {
+            fn args(&self) -> Vec<::serialize::json::Json> {
+                use serialize::json::ToJson;
+                let mut args = vec![$(self.$field.to_json()),*];
+                args.push_all(self.$rest.as_slice());
+                args
+            }
+
+            fn opt_args(&self) -> Option<::serialize::json::JsonObject> {
+                if self.opt_args.is_empty() { None } else { Some(self.opt_args.clone()) }
+            }
+        }
+
+        opt_args_builders! { $name $($opt),+ }
+        to_json_impl! { $name $term_ty }
+    };
     ($name:ident ; $term_ty:expr) => {
         pub struct $name;
 
@@ -84,12 +191,50 @@ macro_rules! term {
             }
         }
 
+        to_json_impl! { $name $term_ty }
+    };
+    // Terms taking a variable number of trailing arguments, e.g. pluck
+    ($name:ident {
+        $($field:ident: $ty:ty),* ; ..$rest:ident
+    } $term_ty:expr) => {
+        pub struct $name {
+            $($field: $ty,)*
+            $rest: Vec<::serialize::json::Json>
+        }
+
+        impl ::query::Term for $name {
+            fn args(&self) -> Vec<::serialize::json::Json> {
+                use serialize::json::ToJson;
+                let mut args = vec![$(self.$field.to_json()),*];
+                args.push_all(self.$rest.as_slice());
+                args
+            }
+        }
+
         to_json_impl! { $name $term_ty }
     };
 }
 
 macro_rules! query {
     // FIXME: can repeating matchers here + in term! be avoided?
+    ($name:ident -> $resp:ty {
+        $($field:ident: $ty:ty),*
+    } opt_args { $($opt:ident),+ } $term_ty:expr) => {
+        term! { $name { $($field: $ty),* } opt_args { $($opt),+ } $term_ty }
+
+        impl<'a> ::query::Query<'a, $resp> for $name {
+            // type R = $resp;
+        }
+    };
+    ($name:ident -> $resp:ty {
+        $($field:ident: $ty:ty),* ; ..$rest:ident
+    } opt_args { $($opt:ident),+ } $term_ty:expr) => {
+        term! { $name { $($field: $ty),* ; ..$rest } opt_args { $($opt),+ } $term_ty }
+
+        impl<'a> ::query::Query<'a, $resp> for $name {
+            // type R = $resp;
+        }
+    };
     ($name:ident -> $resp:ty ; $term_ty:expr) => {
         term! { $name ; $term_ty }
 
@@ -106,6 +251,15 @@ macro_rules! query {
             // type R = $resp;
         }
     };
+    ($name:ident -> $resp:ty {
+        $($field:ident: $ty:ty),* ; ..$rest:ident
+    } $term_ty:expr) => {
+        term! { $name { $($field: $ty),* ; ..$rest } $term_ty }
+
+        impl<'a> ::query::Query<'a, $resp> for $name {
+            // type R = $resp;
+        }
+    };
     // FIXME: term! for enums
     // The extra $()* around the { $($field $ty) } is a hack to make that optional :(
     // How about something like $(...)? ?
@@ -139,11 +293,104 @@ macro_rules! query {
     }
 }
 
+// Implements Expr and ToTerm for terms, along with the arithmetic operators.
+// Coherence won't allow a blanket impl of the std::ops traits for every Expr,
+// so they have to be spelled out per type.
+macro_rules! impl_expr {
+    ($($name:ident),+) => {$(
+        impl ::query::Expr for $name {
+        }
+
+        impl ::query::ToTerm for $name {
+            fn to_term(&self) -> ::serialize::json::Json {
+                use serialize::json::ToJson;
+                self.to_json()
+            }
+        }
+
+        impl<T: ::query::ToTerm> ::std::ops::Add<T, ::query::math::Add> for $name {
+            fn add(&self, rhs: &T) -> ::query::math::Add {
+                use serialize::json::ToJson;
+                use query::ToTerm;
+                ::query::math::add(self.to_json(), rhs.to_term())
+            }
+        }
+
+        impl<T: ::query::ToTerm> ::std::ops::Sub<T, ::query::math::Sub> for $name {
+            fn sub(&self, rhs: &T) -> ::query::math::Sub {
+                use serialize::json::ToJson;
+                use query::ToTerm;
+                ::query::math::sub(self.to_json(), rhs.to_term())
+            }
+        }
+
+        impl<T: ::query::ToTerm> ::std::ops::Mul<T, ::query::math::Mul> for $name {
+            fn mul(&self, rhs: &T) -> ::query::math::Mul {
+                use serialize::json::ToJson;
+                use query::ToTerm;
+                ::query::math::mul(self.to_json(), rhs.to_term())
+            }
+        }
+
+        impl<T: ::query::ToTerm> ::std::ops::Div<T, ::query::math::Div> for $name {
+            fn div(&self, rhs: &T) -> ::query::math::Div {
+                use serialize::json::ToJson;
+                use query::ToTerm;
+                ::query::math::div(self.to_json(), rhs.to_term())
+            }
+        }
+
+        impl<T: ::query::ToTerm> ::std::ops::Rem<T, ::query::math::Mod> for $name {
+            fn rem(&self, rhs: &T) -> ::query::math::Mod {
+                use serialize::json::ToJson;
+                use query::ToTerm;
+                ::query::math::rem(self.to_json(), rhs.to_term())
+            }
+        }
+    )+}
+}
+
+// Selections can have their documents updated or deleted in place. These are
+// inherent methods rather than a trait so that nothing needs to be imported.
+macro_rules! impl_selection {
+    ($($name:ident),+) => {$(
+        impl $name {
+            pub fn update<T: ::query::ToTerm>(self, doc: T) -> ::query::table::Update {
+                use serialize::json::ToJson;
+                use query::ToTerm;
+                ::query::table::update(self.to_json(), doc.to_term())
+            }
+
+            pub fn delete(self) -> ::query::table::Delete {
+                use serialize::json::ToJson;
+                ::query::table::delete(self.to_json())
+            }
+        }
+    )+}
+}
+
+mod aggregate;
+mod binary;
+mod changes;
+mod control;
 pub mod cursor;
 mod db;
+mod ddl;
+mod document;
 mod expr;
+mod fields;
+mod func;
+mod geo;
+mod group;
+mod join;
+mod math;
+mod sequence;
+mod string;
+mod subscription;
 mod table;
-mod term_type;
+#[doc(hidden)]
+pub mod term_type;
+mod time;
 
 // FIXME: this perhaps belongs somewhere else
 // FIXME: having Option<> on everything is really annoying. Can we make RDB
@@ -165,7 +412,7 @@ mod test {
     #[phase(plugin)] extern crate json_macros;
 
     use query as r;
-    use serialize::json::ToJson;
+    use serialize::json::{mod, Json, ToJson};
 
     #[test]
     fn test_db() {
@@ -188,5 +435,391 @@ mod test {
         assert_eq!(r::table("test").index_list().to_json(), json!([77, [[15, ["test"]]]]));
         assert_eq!(r::table("foo").get("bar").delete().to_json(), json!([54, [[16, [[15, ["foo"]], "bar"]]]]));
     }
+
+    #[test]
+    fn test_table_create_opt_args() {
+        assert_eq!(r::table_create("foo").primary_key("name").durability("soft").to_json(),
+                   json!([60, ["foo"], {"durability": "soft", "primary_key": "name"}]));
+        assert_eq!(r::db("bar").table_create("foo").shards(2u).replicas(3u).datacenter("dc1").to_json(),
+                   json!([60, [[14, ["bar"]], "foo"], {"datacenter": "dc1", "replicas": 3, "shards": 2}]));
+    }
+
+    #[test]
+    fn test_chaining() {
+        use query::Expr;
+
+        assert_eq!(r::table("foo").get("bar").add(1i32).to_json(), json!([24, [[16, [[15, ["foo"]], "bar"]], 1]]));
+        assert_eq!((r::table("foo").get("bar") * 2i32).to_json(), json!([26, [[16, [[15, ["foo"]], "bar"]], 2]]));
+        assert_eq!(r::table_list().sub(r::db_list()).to_json(), json!([25, [[62, []], [59, []]]]));
+    }
+
+    #[test]
+    fn test_selection() {
+        assert_eq!(r::table("foo").get("bar").update(json!({"a": 1})).to_json(), json!([53, [[16, [[15, ["foo"]], "bar"]], {"a": 1}]]));
+        assert_eq!(r::table("foo").delete().to_json(), json!([54, [[15, ["foo"]]]]));
+    }
+
+    #[test]
+    fn test_order_by() {
+        use query::Expr;
+
+        assert_eq!(r::table("foo").order_by(&[&r::asc("a"), &r::desc("b")]).to_json(), json!([41, [[15, ["foo"]], [73, ["a"]], [74, ["b"]]]]));
+        assert_eq!(r::table("foo").order_by(&[&"a"]).to_json(), json!([41, [[15, ["foo"]], "a"]]));
+        assert_eq!(r::table("foo").order_by(&[&"a", &r::desc("b")]).to_json(), json!([41, [[15, ["foo"]], "a", [74, ["b"]]]]));
+        assert_eq!(r::table("foo").order_by_index("id").to_json(), json!([41, [[15, ["foo"]]], {"index": "id"}]));
+        assert_eq!(r::table("foo").order_by(&[&"a"]).index(r::desc("id")).to_json(), json!([41, [[15, ["foo"]], "a"], {"index": [74, ["id"]]}]));
+        assert_eq!(r::table("foo").order_by_index(r::desc("id")).delete().to_json(), json!([54, [[41, [[15, ["foo"]]], {"index": [74, ["id"]]}]]]));
+    }
+
+    #[test]
+    fn test_slicing() {
+        use query::{Bound, Expr};
+
+        assert_eq!(r::table("foo").limit(10).to_json(), json!([71, [[15, ["foo"]], 10]]));
+        assert_eq!(r::table("foo").skip(10).to_json(), json!([70, [[15, ["foo"]], 10]]));
+        assert_eq!(r::table("foo").slice(1, 3).to_json(), json!([30, [[15, ["foo"]], 1, 3]]));
+        assert_eq!(r::table("foo").slice(1, 3).right_bound(Bound::Closed).to_json(), json!([30, [[15, ["foo"]], 1, 3], {"right_bound": "closed"}]));
+        assert_eq!(r::table("foo").nth(-1).to_json(), json!([45, [[15, ["foo"]], (-1i)]]));
+        assert_eq!(r::table("foo").sample(5).to_json(), json!([81, [[15, ["foo"]], 5]]));
+        assert_eq!(r::table("foo").is_empty().to_json(), json!([86, [[15, ["foo"]]]]));
+        assert_eq!(r::table("foo").order_by_index("id").limit(5).delete().to_json(), json!([54, [[71, [[41, [[15, ["foo"]]], {"index": "id"}], 5]]]]));
+    }
+
+    // Variable ids come from a global counter, so tests need to find out
+    // which one a function's parameter was given.
+    fn var_id(var: &Json) -> Json {
+        var.as_array().unwrap()[1].as_array().unwrap()[0].clone()
+    }
+
+    #[test]
+    fn test_transformations() {
+        use query::Expr;
+
+        let mut x = json::Null;
+        let q = r::table("foo").map(|doc| { x = doc.to_json(); doc.get_field("a") });
+        assert_eq!(q.to_json(), json!([38, [[15, ["foo"]], [69, [[2, [(var_id(&x))]], [31, [(x), "a"]]]]]]));
+
+        let q = r::table("foo").concat_map(|doc| { x = doc.to_json(); doc.get_field("a") });
+        assert_eq!(q.to_json(), json!([40, [[15, ["foo"]], [69, [[2, [(var_id(&x))]], [31, [(x), "a"]]]]]]));
+
+        let mut y = json::Null;
+        let q = r::table("foo").get_field("n").reduce(|a, b| { x = a.to_json(); y = b.to_json(); a + b });
+        assert_eq!(q.to_json(), json!([37, [[31, [[15, ["foo"]], "n"]],
+                                            [69, [[2, [(var_id(&x)), (var_id(&y))]], [24, [(x), (y)]]]]]]));
+
+        let q = r::table("foo").for_each(|doc| { x = doc.to_json(); r::table("bar").insert(doc) });
+        assert_eq!(q.to_json(), json!([68, [[15, ["foo"]], [69, [[2, [(var_id(&x))]], [56, [[15, ["bar"]], (x)]]]]]]));
+    }
+
+    #[test]
+    fn test_filter() {
+        use query::Expr;
+
+        let mut x = json::Null;
+        let q = r::table("foo").filter(|doc| { x = doc.to_json(); doc.get_field("age").gt(30i32) });
+        assert_eq!(q.to_json(), json!([39, [[15, ["foo"]], [69, [[2, [(var_id(&x))]], [21, [[31, [(x), "age"]], 30]]]]]]));
+        assert_eq!(r::table("foo").filter_by(json!({"age": 30})).to_json(), json!([39, [[15, ["foo"]], {"age": 30}]]));
+        assert_eq!(r::table("foo").filter_by(json!({"age": 30})).delete().to_json(),
+                   json!([54, [[39, [[15, ["foo"]], {"age": 30}]]]]));
+    }
+
+    #[test]
+    fn test_aggregation() {
+        use query::Expr;
+
+        assert_eq!(r::table("foo").count().to_json(), json!([43, [[15, ["foo"]]]]));
+        assert_eq!(r::table("foo").get_field("a").count_by(2i32).to_json(), json!([43, [[31, [[15, ["foo"]], "a"]], 2]]));
+        assert_eq!(r::table("foo").sum().to_json(), json!([145, [[15, ["foo"]]]]));
+        assert_eq!(r::table("foo").sum_by("a").to_json(), json!([145, [[15, ["foo"]], "a"]]));
+        assert_eq!(r::table("foo").avg_by("a").to_json(), json!([146, [[15, ["foo"]], "a"]]));
+        assert_eq!(r::table("foo").min_by("a").to_json(), json!([147, [[15, ["foo"]], "a"]]));
+        assert_eq!(r::table("foo").min().index("a").to_json(), json!([147, [[15, ["foo"]]], {"index": "a"}]));
+        assert_eq!(r::table("foo").max_by("a").to_json(), json!([148, [[15, ["foo"]], "a"]]));
+        assert_eq!(r::table("foo").distinct().to_json(), json!([42, [[15, ["foo"]]]]));
+        assert_eq!(r::table("foo").distinct().index("a").to_json(), json!([42, [[15, ["foo"]]], {"index": "a"}]));
+    }
+
+    #[test]
+    fn test_group() {
+        use query::Expr;
+
+        assert_eq!(r::table("foo").group(&["a"]).to_json(), json!([144, [[15, ["foo"]], "a"]]));
+        assert_eq!(r::table("foo").group(&["a", "b"]).count().to_json(), json!([43, [[144, [[15, ["foo"]], "a", "b"]]]]));
+        assert_eq!(r::table("foo").group::<&str>(&[]).index("a").sum_by("b").to_json(),
+                   json!([145, [[144, [[15, ["foo"]]], {"index": "a"}], "b"]]));
+        assert_eq!(r::table("foo").group(&["a"]).max_by("b").ungroup().to_json(),
+                   json!([150, [[148, [[144, [[15, ["foo"]], "a"]], "b"]]]]));
+        assert_eq!(r::table("foo").group(&["a"]).count().coerce_to("object").to_json(),
+                   json!([51, [[43, [[144, [[15, ["foo"]], "a"]]]], "object"]]));
+
+        let mut x = json::Null;
+        let q = r::table("foo").group(&["a"]).map(|doc| { x = doc.to_json(); doc.get_field("b") }).sum();
+        assert_eq!(q.to_json(), json!([145, [[38, [[144, [[15, ["foo"]], "a"]],
+                                                   [69, [[2, [(var_id(&x))]], [31, [(x), "b"]]]]]]]]));
+        assert_eq!(r::table("foo").group(&["a"]).filter_by(json!({"b": 1})).count().to_json(),
+                   json!([43, [[39, [[144, [[15, ["foo"]], "a"]], {"b": 1}]]]]));
+        assert_eq!(r::table("foo").group(&["a"]).order_by(&[&r::desc("b")]).to_json(),
+                   json!([41, [[144, [[15, ["foo"]], "a"]], [74, ["b"]]]]));
+    }
+
+    #[test]
+    fn test_joins() {
+        use query::Expr;
+
+        let (mut x, mut y) = (json::Null, json::Null);
+        let q = r::table("foo").inner_join(r::table("bar"), |a, b| {
+            x = a.to_json();
+            y = b.to_json();
+            a.get_field("id").eq(b.get_field("foo_id"))
+        });
+        assert_eq!(q.to_json(), json!([48, [[15, ["foo"]], [15, ["bar"]],
+                                            [69, [[2, [(var_id(&x)), (var_id(&y))]],
+                                                  [17, [[31, [(x), "id"]], [31, [(y), "foo_id"]]]]]]]]));
+
+        let q = r::table("foo").outer_join(r::table("bar"), |a, b| {
+            x = a.to_json();
+            y = b.to_json();
+            a.get_field("id").eq(b.get_field("foo_id"))
+        });
+        assert_eq!(q.to_json(), json!([49, [[15, ["foo"]], [15, ["bar"]],
+                                            [69, [[2, [(var_id(&x)), (var_id(&y))]],
+                                                  [17, [[31, [(x), "id"]], [31, [(y), "foo_id"]]]]]]]]));
+
+        assert_eq!(r::table("foo").eq_join("bar_id", r::table("bar")).to_json(),
+                   json!([50, [[15, ["foo"]], "bar_id", [15, ["bar"]]]]));
+        assert_eq!(r::table("foo").eq_join("bar_id", r::table("bar")).index("baz").zip().to_json(),
+                   json!([72, [[50, [[15, ["foo"]], "bar_id", [15, ["bar"]]], {"index": "baz"}]]]));
+    }
+
+    #[test]
+    fn test_document_manipulation() {
+        use query::Expr;
+
+        let doc = || r::table("foo").get("bar");
+        let tags = || r::table("foo").get("bar").get_field("tags");
+
+        assert_eq!(doc().merge(json!({"a": 1})).to_json(), json!([35, [[16, [[15, ["foo"]], "bar"]], {"a": 1}]]));
+        assert_eq!(tags().append("x").to_json(), json!([29, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], "x"]]));
+        assert_eq!(tags().prepend("x").to_json(), json!([80, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], "x"]]));
+        assert_eq!(tags().difference(tags()).to_json(), json!([95, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]],
+                                                                       [31, [[16, [[15, ["foo"]], "bar"]], "tags"]]]]));
+        assert_eq!(tags().set_insert("x").to_json(), json!([88, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], "x"]]));
+        assert_eq!(tags().set_intersection(tags()).to_json(), json!([89, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]],
+                                                                             [31, [[16, [[15, ["foo"]], "bar"]], "tags"]]]]));
+        assert_eq!(tags().set_union(tags()).to_json(), json!([90, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]],
+                                                                      [31, [[16, [[15, ["foo"]], "bar"]], "tags"]]]]));
+        assert_eq!(tags().set_difference(tags()).to_json(), json!([91, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]],
+                                                                           [31, [[16, [[15, ["foo"]], "bar"]], "tags"]]]]));
+        assert_eq!(tags().insert_at(1, "x").to_json(), json!([82, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], 1, "x"]]));
+        assert_eq!(tags().delete_at(1).to_json(), json!([83, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], 1]]));
+        assert_eq!(tags().delete_at_range(1, 3).to_json(), json!([83, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], 1, 3]]));
+        assert_eq!(tags().change_at(1, "x").to_json(), json!([84, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], 1, "x"]]));
+        assert_eq!(tags().splice_at(1, tags()).to_json(), json!([85, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], 1,
+                                                                         [31, [[16, [[15, ["foo"]], "bar"]], "tags"]]]]));
+        assert_eq!(tags().contains("x").to_json(), json!([93, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], "x"]]));
+        assert_eq!(tags().indexes_of("x").to_json(), json!([87, [[31, [[16, [[15, ["foo"]], "bar"]], "tags"]], "x"]]));
+    }
+
+    #[test]
+    fn test_literal() {
+        use query::Expr;
+
+        let doc = || r::table("foo").get("bar");
+
+        assert_eq!(r::literal(json!({"b": 1})).to_json(), json!([137, [{"b": 1}]]));
+        assert_eq!(doc().update(r::make_obj().field("a", r::literal(json!({"b": 1})))).to_json(),
+                   json!([53, [[16, [[15, ["foo"]], "bar"]], [3, [], {"a": [137, [{"b": 1}]]}]]]));
+        assert_eq!(r::empty_literal().to_json(), json!([137, []]));
+        assert_eq!(doc().update(r::make_obj().field("a", r::empty_literal())).to_json(),
+                   json!([53, [[16, [[15, ["foo"]], "bar"]], [3, [], {"a": [137, []]}]]]));
+        assert_eq!(r::make_obj().to_json(), json!([3, [], {}]));
+        assert_eq!(r::make_obj().field("n", r::table("foo").count()).field("s", "x").to_json(),
+                   json!([3, [], {"n": [43, [[15, ["foo"]]]], "s": "x"}]));
+        assert_eq!(r::make_array().to_json(), json!([2, []]));
+        assert_eq!(r::make_array().push(1i32).push(r::table("foo").count()).to_json(),
+                   json!([2, [1, [43, [[15, ["foo"]]]]]]));
+    }
+
+    #[test]
+    fn test_control() {
+        use query::Expr;
+
+        let doc = || r::table("foo").get("bar");
+        let age = || r::table("foo").get("bar").get_field("age");
+
+        assert_eq!(r::branch(doc().has_fields(&["age"]), age(), "unknown").to_json(),
+                   json!([65, [[32, [[16, [[15, ["foo"]], "bar"]], "age"]],
+                               [31, [[16, [[15, ["foo"]], "bar"]], "age"]],
+                               "unknown"]]));
+        assert_eq!(r::error("oops").to_json(), json!([12, ["oops"]]));
+        assert_eq!(age().default(0i32).to_json(), json!([92, [[31, [[16, [[15, ["foo"]], "bar"]], "age"]], 0]]));
+        assert_eq!(r::table("foo").coerce_to("array").to_json(), json!([51, [[15, ["foo"]], "array"]]));
+        assert_eq!(age().type_of().to_json(), json!([52, [[31, [[16, [[15, ["foo"]], "bar"]], "age"]]]]));
+        assert_eq!(r::table("foo").info().to_json(), json!([79, [[15, ["foo"]]]]));
+    }
+
+    #[test]
+    fn test_strings() {
+        use query::Expr;
+
+        let name = || r::table("foo").get("bar").get_field("name");
+
+        assert_eq!(name().match_("^b(o+)").to_json(), json!([97, [[31, [[16, [[15, ["foo"]], "bar"]], "name"]], "^b(o+)"]]));
+        assert_eq!(name().split(None, None).to_json(), json!([149, [[31, [[16, [[15, ["foo"]], "bar"]], "name"]]]]));
+        assert_eq!(name().split(Some(","), None).to_json(), json!([149, [[31, [[16, [[15, ["foo"]], "bar"]], "name"]], ","]]));
+        assert_eq!(name().split(None, Some(2)).to_json(), json!([149, [[31, [[16, [[15, ["foo"]], "bar"]], "name"]], null, 2]]));
+        assert_eq!(name().upcase().to_json(), json!([141, [[31, [[16, [[15, ["foo"]], "bar"]], "name"]]]]));
+        assert_eq!(name().downcase().to_json(), json!([142, [[31, [[16, [[15, ["foo"]], "bar"]], "name"]]]]));
+
+        let mut x = json::Null;
+        let q = r::table("foo").filter(|doc| { x = doc.to_json(); doc.get_field("name").downcase().eq("bob") });
+        assert_eq!(q.to_json(), json!([39, [[15, ["foo"]], [69, [[2, [(var_id(&x))]],
+                                                              [17, [[142, [[31, [(x), "name"]]]], "bob"]]]]]]));
+        let q = r::table("foo").filter(|doc| { x = doc.to_json(); doc.get_field("name").match_("^b").ne(json::Null) });
+        assert_eq!(q.to_json(), json!([39, [[15, ["foo"]], [69, [[2, [(var_id(&x))]],
+                                                              [18, [[97, [[31, [(x), "name"]], "^b"]], null]]]]]]));
+    }
+
+    #[test]
+    fn test_changes() {
+        use query::Expr;
+
+        assert_eq!(r::table("foo").changes().to_json(), json!([152, [[15, ["foo"]]]]));
+        assert_eq!(r::table("foo").get("bar").changes().to_json(), json!([152, [[16, [[15, ["foo"]], "bar"]]]]));
+        assert_eq!(r::table("foo").order_by_index(r::desc("score")).limit(3).changes().to_json(),
+                   json!([152, [[71, [[41, [[15, ["foo"]]], {"index": [74, ["score"]]}], 3]]]]));
+        assert_eq!(r::table("foo").filter_by(json!({"team": "red"})).changes().to_json(),
+                   json!([152, [[39, [[15, ["foo"]], {"team": "red"}]]]]));
+    }
+
+    #[test]
+    fn test_binary() {
+        let thumbnail = r::Binary(vec![0xff, 0xd8, 0xff]);
+        assert_eq!(r::table("foo").insert(json!({"thumbnail": (thumbnail)})).to_json(),
+                   json!([56, [[15, ["foo"]], {"thumbnail": {"$reql_type$": "BINARY", "data": "/9j/"}}]]));
+        assert_eq!(r::binary("it stinks!").to_json(), json!([155, ["it stinks!"]]));
+    }
+
+    #[test]
+    fn test_geo() {
+        use query::Expr;
+
+        let office = || r::point(-122.423246, 37.779388);
+        assert_eq!(office().to_json(), json!([159, [(-122.423246f64), 37.779388]]));
+        assert_eq!(r::line(&[r::point(0.0, 0.0), r::point(1.0, 1.0)]).to_json(),
+                   json!([160, [[159, [0.0, 0.0]], [159, [1.0, 1.0]]]]));
+        assert_eq!(r::polygon(&[r::point(0.0, 0.0), r::point(0.0, 1.0), r::point(1.0, 1.0)]).to_json(),
+                   json!([161, [[159, [0.0, 0.0]], [159, [0.0, 1.0]], [159, [1.0, 1.0]]]]));
+        assert_eq!(r::circle(office(), 1000.0).unit("m").num_vertices(64u).to_json(),
+                   json!([165, [[159, [(-122.423246f64), 37.779388]], 1000.0], {"num_vertices": 64, "unit": "m"}]));
+        assert_eq!(r::geojson(json!({"type": "Point", "coordinates": [0, 0]})).to_json(),
+                   json!([157, [{"type": "Point", "coordinates": [2, [0, 0]]}]]));
+
+        let p = || r::point(0.0, 0.0);
+        assert_eq!(p().to_geojson().to_json(), json!([158, [[159, [0.0, 0.0]]]]));
+        assert_eq!(p().distance(p()).unit("km").to_json(),
+                   json!([162, [[159, [0.0, 0.0]], [159, [0.0, 0.0]]], {"unit": "km"}]));
+        assert_eq!(p().intersects(p()).to_json(), json!([163, [[159, [0.0, 0.0]], [159, [0.0, 0.0]]]]));
+        assert_eq!(r::table("foo").get_field("area").includes(p()).to_json(),
+                   json!([164, [[31, [[15, ["foo"]], "area"]], [159, [0.0, 0.0]]]]));
+        assert_eq!(r::line(&[p(), r::point(1.0, 1.0)]).fill().to_json(),
+                   json!([167, [[160, [[159, [0.0, 0.0]], [159, [1.0, 1.0]]]]]]));
+        assert_eq!(r::polygon(&[p()]).polygon_sub(r::polygon(&[p()])).to_json(),
+                   json!([171, [[161, [[159, [0.0, 0.0]]]], [161, [[159, [0.0, 0.0]]]]]]));
+
+        assert_eq!(r::table("foo").get_intersecting(r::circle(p(), 10.0), "location").to_json(),
+                   json!([166, [[15, ["foo"]], [165, [[159, [0.0, 0.0]], 10.0]]], {"index": "location"}]));
+        assert_eq!(r::table("foo").get_nearest(p(), "location").max_results(5u).to_json(),
+                   json!([168, [[15, ["foo"]], [159, [0.0, 0.0]]], {"index": "location", "max_results": 5}]));
+        assert_eq!(r::table("foo").index_create("location").geo(true).to_json(),
+                   json!([75, [[15, ["foo"]], "location"], {"geo": true}]));
+
+        let office = r::Point::new(-122.423246, 37.779388);
+        assert_eq!(r::table("foo").insert(json!({"location": (office)})).to_json(),
+                   json!([56, [[15, ["foo"]], {"location": {"$reql_type$": "GEOMETRY", "type": "Point",
+                                                            "coordinates": [2, [(-122.423246f64), 37.779388]]}}]]));
+        assert_eq!(r::line(&[office.clone(), r::Point::new(0.0, 0.0)]).to_json(),
+                   json!([160, [{"$reql_type$": "GEOMETRY", "type": "Point",
+                                 "coordinates": [2, [(-122.423246f64), 37.779388]]},
+                                {"$reql_type$": "GEOMETRY", "type": "Point", "coordinates": [2, [0.0, 0.0]]}]]));
+    }
+
+    #[test]
+    fn test_time() {
+        use query::Expr;
+
+        assert_eq!(r::now().to_json(), json!([103, []]));
+        assert_eq!(r::time(2014, 11, 15, "Z").to_json(), json!([136, [2014, 11, 15, "Z"]]));
+        assert_eq!(r::time_hms(2014, 11, 15, 8, 30, 15.5, "-08:00").to_json(),
+                   json!([136, [2014, 11, 15, 8, 30, 15.5, "-08:00"]]));
+        assert_eq!(r::epoch_time(1416009600.0).to_json(), json!([101, [1416009600.0]]));
+        assert_eq!(r::iso8601("2014-11-15T08:30:00").default_timezone("-08:00").to_json(),
+                   json!([99, ["2014-11-15T08:30:00"], {"default_timezone": "-08:00"}]));
+        assert_eq!(r::now().to_iso8601().to_json(), json!([100, [[103, []]]]));
+        assert_eq!(r::now().to_epoch_time().to_json(), json!([102, [[103, []]]]));
+        assert_eq!(r::now().in_timezone("+01:00").to_json(), json!([104, [[103, []], "+01:00"]]));
+        assert_eq!(r::now().during(r::time(2014, 1, 1, "Z"), r::time(2015, 1, 1, "Z"))
+                           .right_bound(r::Bound::Closed).to_json(),
+                   json!([105, [[103, []], [136, [2014, 1, 1, "Z"]], [136, [2015, 1, 1, "Z"]]],
+                          {"right_bound": "closed"}]));
+        assert_eq!(r::now().date().to_json(), json!([106, [[103, []]]]));
+        assert_eq!(r::now().time_of_day().to_json(), json!([126, [[103, []]]]));
+        assert_eq!(r::now().timezone().to_json(), json!([127, [[103, []]]]));
+        assert_eq!(r::now().year().to_json(), json!([128, [[103, []]]]));
+        assert_eq!(r::now().month().to_json(), json!([129, [[103, []]]]));
+        assert_eq!(r::now().day().to_json(), json!([130, [[103, []]]]));
+        assert_eq!(r::now().day_of_week().to_json(), json!([131, [[103, []]]]));
+        assert_eq!(r::now().day_of_year().to_json(), json!([132, [[103, []]]]));
+        assert_eq!(r::now().hours().to_json(), json!([133, [[103, []]]]));
+        assert_eq!(r::now().minutes().to_json(), json!([134, [[103, []]]]));
+        assert_eq!(r::now().seconds().to_json(), json!([135, [[103, []]]]));
+
+        let t = r::Time::from_epoch_time(1416009600.0);
+        assert_eq!(r::table("foo").insert(json!({"at": (t)})).to_json(),
+                   json!([56, [[15, ["foo"]], {"at": {"$reql_type$": "TIME", "epoch_time": 1416009600.0,
+                                                      "timezone": "+00:00"}}]]));
+    }
+
+    #[test]
+    fn test_index_create() {
+        assert_eq!(r::table("foo").index_create("tags").multi(true).to_json(),
+                   json!([75, [[15, ["foo"]], "tags"], {"multi": true}]));
+
+        let mut x = json::Null;
+        let q = r::table("foo").index_create_with("total", r::func(|doc| { x = doc.to_json(); doc + 1i32 }));
+        assert_eq!(q.to_json(), json!([75, [[15, ["foo"]], "total", [69, [[2, [(var_id(&x))]], [24, [(x), 1]]]]]]));
+
+        let q = r::table("foo").index_create_compound("name", &["last", "first"]);
+        let x = q.to_json();
+        let x = &x.as_array().unwrap()[1].as_array().unwrap()[2];
+        let doc = var_id(&x.as_array().unwrap()[1].as_array().unwrap()[0]);
+        assert_eq!(*x, json!([69, [[2, [(doc)]], [2, [[31, [[10, [(doc)]], "last"]], [31, [[10, [(doc)]], "first"]]]]]]));
+
+        assert_eq!(r::table("foo").index_rename("a", "b").overwrite(true).to_json(),
+                   json!([156, [[15, ["foo"]], "a", "b"], {"overwrite": true}]));
+    }
+
+    #[test]
+    fn test_index_status() {
+        assert_eq!(r::table("foo").index_status(&[]).to_json(), json!([139, [[15, ["foo"]]]]));
+        assert_eq!(r::table("foo").index_status(&["a", "b"]).to_json(), json!([139, [[15, ["foo"]], "a", "b"]]));
+        assert_eq!(r::table("foo").index_wait(&[]).to_json(), json!([140, [[15, ["foo"]]]]));
+        assert_eq!(r::table("foo").index_wait(&["a"]).to_json(), json!([140, [[15, ["foo"]], "a"]]));
+    }
+
+    #[test]
+    fn test_fields() {
+        use query::Expr;
+
+        assert_eq!(r::table("foo").get("bar").get_field("baz").to_json(), json!([31, [[16, [[15, ["foo"]], "bar"]], "baz"]]));
+        assert_eq!(r::expr(json!({"a": 1})).bracket("a").to_json(), json!([170, [{"a": 1}, "a"]]));
+        assert_eq!(r::table("foo").pluck(&["a", "b"]).to_json(), json!([33, [[15, ["foo"]], "a", "b"]]));
+        assert_eq!(r::table("foo").get("bar").pluck(&[json!("a"), json!({"b": ["c", "d"]})]).to_json(),
+                   json!([33, [[16, [[15, ["foo"]], "bar"]], "a", {"b": [2, ["c", "d"]]}]]));
+        assert_eq!(r::table("foo").without(&[json!({"b": {"c": true}})]).to_json(), json!([34, [[15, ["foo"]], {"b": {"c": true}}]]));
+        assert_eq!(r::table("foo").get("bar").has_fields(&["a"]).to_json(), json!([32, [[16, [[15, ["foo"]], "bar"]], "a"]]));
+        assert_eq!(r::table("foo").with_fields(&["a", "b"]).to_json(), json!([96, [[15, ["foo"]], "a", "b"]]));
+        assert_eq!(r::table("foo").get("bar").keys().to_json(), json!([94, [[16, [[15, ["foo"]], "bar"]]]]));
+    }
 }
 
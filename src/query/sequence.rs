@@ -0,0 +1,261 @@
+use std::collections::TreeMap;
+
+use serialize::json::{Json, ToJson};
+
+use super::{ToTerm, Writes};
+use super::cursor::Cursor;
+use super::func::Func;
+use super::term_type as ty;
+use super::term_type::TermType;
+
+/// A key for `order_by` with an explicit direction; see `asc` and `desc`.
+pub struct Order {
+    direction: TermType,
+    key: Json
+}
+
+impl ::query::Term for Order {
+    fn args(&self) -> Vec<Json> {
+        vec![self.key.clone()]
+    }
+}
+
+impl ToJson for Order {
+    fn to_json(&self) -> Json {
+        use query::Term;
+        (self.direction, self.args()).to_json()
+    }
+}
+
+impl ToTerm for Order {
+    fn to_term(&self) -> Json {
+        self.to_json()
+    }
+}
+
+/// Orders ascending by a field name or function.
+pub fn asc<T: ToTerm>(key: T) -> Order {
+    Order { direction: ty::ASC, key: key.to_term() }
+}
+
+/// Orders descending by a field name or function.
+pub fn desc<T: ToTerm>(key: T) -> Order {
+    Order { direction: ty::DESC, key: key.to_term() }
+}
+
+term! {
+    OrderBy {
+        seq: Json ; ..keys
+    } opt_args { index } ty::ORDER_BY
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for OrderBy {
+}
+
+pub fn order_by(seq: Json, keys: &[&ToTerm]) -> OrderBy {
+    OrderBy {
+        seq: seq,
+        keys: keys.iter().map(|x| x.to_term()).collect(),
+        opt_args: TreeMap::new()
+    }
+}
+
+impl_expr! { OrderBy }
+impl_selection! { OrderBy }
+
+term! {
+    Limit {
+        seq: Json,
+        n: uint
+    } ty::LIMIT
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Limit {
+}
+
+pub fn limit(seq: Json, n: uint) -> Limit {
+    Limit { seq: seq, n: n }
+}
+
+term! {
+    Skip {
+        seq: Json,
+        n: uint
+    } ty::SKIP
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Skip {
+}
+
+pub fn skip(seq: Json, n: uint) -> Skip {
+    Skip { seq: seq, n: n }
+}
+
+/// Whether a bound of `slice` includes the element at that index. By default
+/// the left bound is closed and the right bound is open.
+pub enum Bound {
+    Open,
+    Closed
+}
+
+impl ToJson for Bound {
+    fn to_json(&self) -> Json {
+        match *self {
+            Bound::Open => "open",
+            Bound::Closed => "closed"
+        }.to_json()
+    }
+}
+
+impl ToTerm for Bound {
+    fn to_term(&self) -> Json {
+        self.to_json()
+    }
+}
+
+term! {
+    Slice {
+        seq: Json,
+        start: int,
+        end: int
+    } opt_args { left_bound, right_bound } ty::SLICE
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Slice {
+}
+
+pub fn slice(seq: Json, start: int, end: int) -> Slice {
+    Slice { seq: seq, start: start, end: end, opt_args: TreeMap::new() }
+}
+
+query! {
+    Nth -> Json {
+        seq: Json,
+        index: int
+    } ty::NTH
+}
+
+pub fn nth(seq: Json, index: int) -> Nth {
+    Nth { seq: seq, index: index }
+}
+
+term! {
+    Sample {
+        seq: Json,
+        n: uint
+    } ty::SAMPLE
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Sample {
+}
+
+pub fn sample(seq: Json, n: uint) -> Sample {
+    Sample { seq: seq, n: n }
+}
+
+query! {
+    IsEmpty -> bool {
+        seq: Json
+    } ty::IS_EMPTY
+}
+
+pub fn is_empty(seq: Json) -> IsEmpty {
+    IsEmpty { seq: seq }
+}
+
+impl_expr! { Limit, Skip, Slice, Nth, Sample, IsEmpty }
+impl_selection! { Limit, Skip, Slice, Nth, Sample }
+
+term! {
+    Map {
+        seq: Json,
+        func: Func
+    } ty::MAP
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Map {
+}
+
+pub fn map(seq: Json, func: Func) -> Map {
+    Map { seq: seq, func: func }
+}
+
+term! {
+    ConcatMap {
+        seq: Json,
+        func: Func
+    } ty::CONCAT_MAP
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for ConcatMap {
+}
+
+pub fn concat_map(seq: Json, func: Func) -> ConcatMap {
+    ConcatMap { seq: seq, func: func }
+}
+
+query! {
+    Reduce -> Json {
+        seq: Json,
+        func: Func
+    } ty::REDUCE
+}
+
+pub fn reduce(seq: Json, func: Func) -> Reduce {
+    Reduce { seq: seq, func: func }
+}
+
+query! {
+    ForEach -> Writes {
+        seq: Json,
+        func: Func
+    } ty::FOR_EACH
+}
+
+pub fn for_each(seq: Json, func: Func) -> ForEach {
+    ForEach { seq: seq, func: func }
+}
+
+term! {
+    Filter {
+        seq: Json,
+        predicate: Json
+    } ty::FILTER
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for Filter {
+}
+
+pub fn filter(seq: Json, predicate: Json) -> Filter {
+    Filter { seq: seq, predicate: predicate }
+}
+
+impl_expr! { Map, ConcatMap, Reduce, ForEach, Filter }
+impl_selection! { Filter }
+
+query! {
+    Contains -> bool {
+        seq: Json,
+        value: Json
+    } ty::CONTAINS
+}
+
+pub fn contains(seq: Json, value: Json) -> Contains {
+    Contains { seq: seq, value: value }
+}
+
+term! {
+    IndexesOf {
+        seq: Json,
+        value: Json
+    } ty::INDEXES_OF
+}
+
+impl<'a> ::query::Query<'a, Cursor<'a>> for IndexesOf {
+}
+
+pub fn indexes_of(seq: Json, value: Json) -> IndexesOf {
+    IndexesOf { seq: seq, value: value }
+}
+
+impl_expr! { Contains, IndexesOf }
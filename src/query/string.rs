@@ -0,0 +1,107 @@
+use serialize::json::{mod, Json};
+
+use errors::RdbResult;
+use from_response::{mod, FromResponse};
+use net::{Connection, Response};
+
+use super::term_type as ty;
+
+query! {
+    Match -> Option<MatchResult> {
+        string: Json,
+        regex: String
+    } ty::MATCH
+}
+
+pub fn match_(string: Json, regex: &str) -> Match {
+    Match { string: string, regex: regex.into_string() }
+}
+
+/// The result of a successful `match`: the matched substring and its
+/// position, along with each of the regular expression's capture groups.
+#[deriving(Decodable, Show, PartialEq)]
+pub struct MatchResult {
+    pub str: String,
+    pub start: u64,
+    pub end: u64,
+    /// `None` for groups which didn't participate in the match.
+    pub groups: Vec<Option<MatchGroup>>
+}
+
+#[deriving(Decodable, Show, PartialEq)]
+pub struct MatchGroup {
+    pub str: String,
+    pub start: u64,
+    pub end: u64
+}
+
+// ReQL's match evaluates to null when the string doesn't match.
+impl<'a> FromResponse<'a> for Option<MatchResult> {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Option<MatchResult>> {
+        match try!(from_response::atom(res)) {
+            json::Null => Ok(None),
+            datum => Ok(Some(try!(from_response::decode(datum))))
+        }
+    }
+}
+
+query! {
+    Split -> Vec<String> {
+        string: Json ; ..args
+    } ty::SPLIT
+}
+
+pub fn split(string: Json, separator: Option<&str>, max_splits: Option<uint>) -> Split {
+    use serialize::json::ToJson;
+
+    let separator = separator.map(|s| json::String(s.into_string())).unwrap_or(json::Null);
+    let args = match max_splits {
+        Some(n) => vec![separator, n.to_json()],
+        None if separator.is_null() => vec![],
+        None => vec![separator]
+    };
+    Split { string: string, args: args }
+}
+
+query! {
+    Upcase -> String {
+        string: Json
+    } ty::UPCASE
+}
+
+pub fn upcase(string: Json) -> Upcase {
+    Upcase { string: string }
+}
+
+query! {
+    Downcase -> String {
+        string: Json
+    } ty::DOWNCASE
+}
+
+pub fn downcase(string: Json) -> Downcase {
+    Downcase { string: string }
+}
+
+impl_expr! { Match, Split, Upcase, Downcase }
+
+#[cfg(test)]
+mod test {
+    use from_response;
+    use serialize::json;
+
+    use super::{MatchResult, MatchGroup};
+
+    #[test]
+    fn test_match_result_decode() {
+        let datum = json::from_str(r#"{"str": "bob@example.com", "start": 4, "end": 19,
+                                       "groups": [{"str": "bob", "start": 4, "end": 7}, null]}"#).unwrap();
+        let result: MatchResult = from_response::decode(datum).unwrap();
+        assert_eq!(result, MatchResult {
+            str: "bob@example.com".to_string(),
+            start: 4,
+            end: 19,
+            groups: vec![Some(MatchGroup { str: "bob".to_string(), start: 4, end: 7 }), None]
+        });
+    }
+}
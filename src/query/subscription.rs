@@ -0,0 +1,241 @@
+use std::io::net::tcp::TcpStream;
+use std::io::timer;
+use std::sync::{Arc, Mutex};
+use std::task;
+use std::time::Duration;
+
+use serialize::Decodable;
+use serialize::json::{mod, ToJson};
+
+use errors::{Error, RdbResult};
+use from_response::FromResponse;
+use net::{mod, Connection, ResponseKind};
+
+use super::changes::{Change, ChangeFeed, Changes};
+
+/// A changefeed running in a task of its own, which sends each change to a
+/// channel.
+///
+/// The task owns its own connection. If that connection is lost, it
+/// reconnects and starts the changefeed again; changes made while it was
+/// disconnected are missed. The channel hangs up when the changefeed ends,
+/// fails with anything but an IO error, or is unsubscribed from.
+pub struct Subscription<T: Send> {
+    receiver: Receiver<Change<T>>,
+    shutdown: Arc<Mutex<Shutdown>>
+}
+
+struct Shutdown {
+    stopped: bool,
+    // The socket of the task's current connection
+    socket: Option<TcpStream>
+}
+
+impl Shutdown {
+    fn stop(&mut self) {
+        self.stopped = true;
+        // Shutting down the socket wakes the task if it's waiting for changes
+        if let Some(ref mut socket) = self.socket {
+            let _ = socket.close_read();
+        }
+    }
+}
+
+impl<T: Send> Subscription<T> {
+    pub fn receiver(&self) -> &Receiver<Change<T>> {
+        &self.receiver
+    }
+
+    /// Stops the changefeed and closes its connection. Dropping the
+    /// subscription does the same.
+    pub fn unsubscribe(self) {
+        self.shutdown.lock().stop();
+    }
+}
+
+#[unsafe_destructor]
+impl<T: Send> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        self.shutdown.lock().stop();
+    }
+}
+
+impl Changes {
+    /// Runs this changefeed in a new task, on a new connection to `host`
+    /// and `port`. That connection doesn't share the settings of any other,
+    /// so `Connection::use_db` doesn't apply to it: the table being watched
+    /// should name its database, e.g.
+    /// `r::db("app").table("sessions").changes().subscribe("localhost", 28015)`
+    pub fn subscribe<T>(self, host: &str, port: u16) -> RdbResult<Subscription<T>>
+        where T: Decodable<json::Decoder, json::DecoderError> + Send {
+        use errors::Error::DriverError;
+
+        // Start the changefeed here, so that bad queries and unreachable
+        // servers are reported to the caller.
+        let term = self.to_json();
+        let mut conn = try!(net::connect(host, port));
+        let res = try!(net::run(&mut conn, term.clone()));
+        if res.kind == ResponseKind::Atom {
+            return Err(DriverError(format!("expected a changefeed, got {}", res)));
+        }
+
+        let (sender, receiver) = channel();
+        let shutdown = Arc::new(Mutex::new(Shutdown {
+            stopped: false,
+            socket: Some(net::socket(&conn))
+        }));
+        let task_shutdown = shutdown.clone();
+        let host = host.into_string();
+
+        task::spawn(proc() {
+            let mut conn = conn;
+            let mut res = Some(res);
+            loop {
+                let err = {
+                    let res = match res.take() {
+                        Some(res) => Ok(res),
+                        None => net::run(&mut conn, term.clone())
+                    };
+                    let feed: RdbResult<ChangeFeed<T>> = match res {
+                        Ok(res) => FromResponse::from_response(res, &mut conn),
+                        Err(e) => Err(e)
+                    };
+                    pump(feed, &sender)
+                };
+                match err {
+                    Some(Error::IoError(e)) => {
+                        if task_shutdown.lock().stopped {
+                            return;
+                        }
+                        debug!("Changefeed connection lost, reconnecting: {}", e);
+                        conn = match reconnect(host.as_slice(), port, &*task_shutdown) {
+                            Some(conn) => conn,
+                            None => return
+                        };
+                    }
+                    Some(e) => {
+                        debug!("Changefeed failed: {}", e);
+                        return;
+                    }
+                    None => return
+                }
+            }
+        });
+
+        Ok(Subscription { receiver: receiver, shutdown: shutdown })
+    }
+}
+
+// Sends changes from a changefeed until it ends or the receiver hangs up,
+// returning None, or it fails.
+fn pump<T, I>(changes: RdbResult<I>, sender: &Sender<Change<T>>) -> Option<Error>
+    where T: Send, I: Iterator<RdbResult<Change<T>>> {
+    let changes = match changes {
+        Ok(changes) => changes,
+        Err(e) => return Some(e)
+    };
+    for change in changes {
+        match change {
+            Ok(change) => {
+                if sender.send_opt(change).is_err() {
+                    return None;
+                }
+            }
+            Err(e) => return Some(e)
+        }
+    }
+    None
+}
+
+// Tries to reconnect every second, until it succeeds or the subscription is
+// stopped.
+fn reconnect(host: &str, port: u16, shutdown: &Mutex<Shutdown>) -> Option<Connection> {
+    loop {
+        timer::sleep(Duration::seconds(1));
+        if shutdown.lock().stopped {
+            return None;
+        }
+        match net::connect(host, port) {
+            Ok(conn) => {
+                let mut shutdown = shutdown.lock();
+                if shutdown.stopped {
+                    return None;
+                }
+                shutdown.socket = Some(net::socket(&conn));
+                return Some(conn);
+            }
+            Err(e) => debug!("Couldn't reconnect changefeed: {}", e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Listener, Acceptor};
+    use std::io::net::tcp::{TcpListener, TcpStream};
+
+    use errors::{Error, RdbResult};
+    use query::changes::Change;
+
+    use super::{Shutdown, pump};
+
+    fn change(n: int) -> RdbResult<Change<int>> {
+        Ok(Change { old_val: None, new_val: Some(n) })
+    }
+
+    #[test]
+    fn test_pump() {
+        let (sender, receiver) = channel();
+        let changes = vec![change(1), change(2)];
+        assert!(pump(Ok(changes.into_iter()), &sender).is_none());
+        assert_eq!(receiver.recv().new_val, Some(1));
+        assert_eq!(receiver.recv().new_val, Some(2));
+        drop(sender);
+        assert!(receiver.recv_opt().is_err());
+    }
+
+    #[test]
+    fn test_pump_error() {
+        let (sender, receiver) = channel();
+        let err: RdbResult<Change<int>> = Err(Error::DriverError("boom".into_string()));
+        let changes = vec![change(1), err, change(2)];
+        match pump(Ok(changes.into_iter()), &sender) {
+            Some(Error::DriverError(ref msg)) if msg.as_slice() == "boom" => {}
+            e => panic!("expected the feed's error, got {}", e)
+        }
+        assert_eq!(receiver.recv().new_val, Some(1));
+        assert!(receiver.try_recv().is_err());
+
+        let failed: RdbResult<::std::vec::MoveItems<RdbResult<Change<int>>>> =
+            Err(Error::RuntimeError("no such table".into_string()));
+        assert!(pump(failed, &sender).is_some());
+    }
+
+    #[test]
+    fn test_pump_hang_up() {
+        let (sender, receiver) = channel();
+        drop(receiver);
+        let mut changes = vec![change(1), change(2)].into_iter();
+        assert!(pump(Ok(changes.by_ref()), &sender).is_none());
+        assert_eq!(changes.count(), 1);
+    }
+
+    #[test]
+    fn test_stop() {
+        let mut listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.socket_name().unwrap();
+        let mut acceptor = listener.listen().unwrap();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let _server = acceptor.accept().unwrap();
+
+        let mut shutdown = Shutdown { stopped: false, socket: Some(stream.clone()) };
+        shutdown.stop();
+        assert!(shutdown.stopped);
+        // Reads see the end of the stream, rather than blocking
+        assert!(stream.read(&mut [0u8]).is_err());
+
+        let mut shutdown = Shutdown { stopped: false, socket: None };
+        shutdown.stop();
+        assert!(shutdown.stopped);
+    }
+}
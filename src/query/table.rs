@@ -1,23 +1,67 @@
-use serialize::json;
+use std::collections::TreeMap;
 
-use super::Writes;
+use serialize::json::{mod, ToJson};
+
+use errors::RdbResult;
+use from_response::{mod, FromResponse};
+use net::{Connection, Response};
+
+use super::{ToTerm, Writes};
+use super::binary::Binary;
 use super::db::Db;
+use super::ddl::{Created, Dropped, Renamed};
+use super::document;
+use super::fields;
+use super::func::func;
+use super::geo::{mod, GetIntersecting, GetNearest};
+use super::sequence::{mod, OrderBy};
 use super::term_type as ty;
 use super::cursor::Cursor;
 
-query! {
-    enum TableCreate -> () {
-        TableCreate1 { name: String },
-        TableCreate2 { db: Db, name: String }
-    } ty::TABLE_CREATE
+// Done by hand because query! can't give enums optional arguments
+pub enum TableCreate {
+    TableCreate1 { name: String, opt_args: json::JsonObject },
+    TableCreate2 { db: Db, name: String, opt_args: json::JsonObject }
+}
+
+impl ::query::Term for TableCreate {
+    fn args(&self) -> Vec<json::Json> {
+        match *self {
+            TableCreate::TableCreate1 { ref name, .. } => vec![name.to_json()],
+            TableCreate::TableCreate2 { ref db, ref name, .. } => vec![db.to_json(), name.to_json()]
+        }
+    }
+
+    fn opt_args(&self) -> Option<json::JsonObject> {
+        let opt_args = match *self {
+            TableCreate::TableCreate1 { ref opt_args, .. } => opt_args,
+            TableCreate::TableCreate2 { ref opt_args, .. } => opt_args
+        };
+        if opt_args.is_empty() { None } else { Some(opt_args.clone()) }
+    }
+}
+
+impl TableCreate {
+    fn opt_args_mut(&mut self) -> &mut json::JsonObject {
+        match *self {
+            TableCreate::TableCreate1 { ref mut opt_args, .. } => opt_args,
+            TableCreate::TableCreate2 { ref mut opt_args, .. } => opt_args
+        }
+    }
+}
+
+opt_args_builders! { enum TableCreate primary_key, durability, shards, replicas, datacenter }
+to_json_impl! { TableCreate ty::TABLE_CREATE }
+
+impl<'a> ::query::Query<'a, Created> for TableCreate {
 }
 
 pub fn table_create(name: &str) -> TableCreate {
-    TableCreate::TableCreate1 { name: name.into_string() }
+    TableCreate::TableCreate1 { name: name.into_string(), opt_args: TreeMap::new() }
 }
 
 query! {
-    enum TableDrop -> () {
+    enum TableDrop -> Dropped {
         TableDrop1 { name: String},
         TableDrop2 { db: Db, name: String }
     } ty::TABLE_DROP
@@ -73,16 +117,53 @@ pub fn table(name: &str) -> Table {
 }
 
 impl Table {
-    pub fn get(self, key: &str) -> Get {
-        Get { table: self, key: key.into_string() }
+    pub fn get<T: ToTerm>(self, key: T) -> Get {
+        Get { table: self, key: key.to_term() }
     }
 
-    pub fn insert(self, document: json::Json) -> Insert {
-        Insert { table: self, document: document }
+    pub fn insert<T: ToTerm>(self, document: T) -> Insert {
+        Insert { table: self, document: document.to_term() }
     }
 
+    /// Creates a secondary index on the field `name`. Use `IndexCreate::geo`
+    /// to index geometry, and `IndexCreate::multi` to index each element of
+    /// an array separately.
     pub fn index_create(self, name: &str) -> IndexCreate {
-        IndexCreate { table: self, name: name.into_string() }
+        IndexCreate { table: self, name: name.into_string(), func: vec![], opt_args: TreeMap::new() }
+    }
+
+    /// Creates a secondary index on the value of `function` (see `r::func`)
+    /// for each document, e.g.
+    /// `index_create_with("full_name", r::func(|p| p.get_field("first") + p.get_field("last")))`
+    pub fn index_create_with<T: ToTerm>(self, name: &str, function: T) -> IndexCreate {
+        IndexCreate {
+            table: self,
+            name: name.into_string(),
+            func: vec![function.to_term()],
+            opt_args: TreeMap::new()
+        }
+    }
+
+    /// Creates a compound index on the given fields, which can be queried
+    /// with arrays of their values.
+    pub fn index_create_compound(self, name: &str, fields: &[&str]) -> IndexCreate {
+        let function = func(|doc| {
+            fields.iter().fold(document::make_array(), |array, field| {
+                array.push(fields::get_field(doc.to_json(), *field))
+            })
+        });
+        self.index_create_with(name, function)
+    }
+
+    /// Renames the index `old_name`. Use `IndexRename::overwrite` to replace
+    /// any index already named `new_name`.
+    pub fn index_rename(self, old_name: &str, new_name: &str) -> IndexRename {
+        IndexRename {
+            table: self,
+            old_name: old_name.into_string(),
+            new_name: new_name.into_string(),
+            opt_args: TreeMap::new()
+        }
     }
 
     pub fn index_drop(self, name: &str) -> IndexDrop {
@@ -92,6 +173,40 @@ impl Table {
     pub fn index_list(self) -> IndexList {
         IndexList { table: self }
     }
+
+    /// Gets the status of the given secondary indexes, or of all of them if
+    /// `names` is empty.
+    pub fn index_status(self, names: &[&str]) -> IndexStatusTerm {
+        IndexStatusTerm { table: self, names: names.iter().map(|x| x.to_term()).collect() }
+    }
+
+    /// Waits until the given secondary indexes, or all of them if `names` is
+    /// empty, are ready, then gets their status.
+    pub fn index_wait(self, names: &[&str]) -> IndexWait {
+        IndexWait { table: self, names: names.iter().map(|x| x.to_term()).collect() }
+    }
+
+    /// Orders the table by a secondary index (or the primary key). This is
+    /// much faster than sorting by a field, and isn't limited in the number
+    /// of documents it can sort. Wrap the index name in `r::desc` to sort
+    /// descending. Ordinary keys can be added as tie-breakers with `order_by`
+    /// followed by `OrderBy::index`.
+    pub fn order_by_index<T: ToTerm>(self, index: T) -> OrderBy {
+        let keys: &[&ToTerm] = &[];
+        sequence::order_by(self.to_json(), keys).index(index)
+    }
+
+    /// Gets the documents whose geometry, in the geospatial `index`,
+    /// intersects `geometry`.
+    pub fn get_intersecting<T: ToTerm>(self, geometry: T, index: &str) -> GetIntersecting {
+        geo::get_intersecting(self.to_json(), geometry.to_term(), index)
+    }
+
+    /// Gets the documents nearest to `point` in the geospatial `index`, with
+    /// their distances from it. These can be decoded as `r::Nearest`.
+    pub fn get_nearest<T: ToTerm>(self, point: T, index: &str) -> GetNearest {
+        geo::get_nearest(self.to_json(), point.to_term(), index)
+    }
 }
 
 query! {
@@ -99,16 +214,10 @@ query! {
     // responses directly into structs
     Get -> json::Json {
         table: Table,
-        key: String
+        key: json::Json
     } ty::GET
 }
 
-impl Get {
-    pub fn delete(self) -> Delete {
-        Delete::DeleteGet { get: self }
-    }
-}
-
 query! {
     Insert -> Writes {
         table: Table,
@@ -117,23 +226,43 @@ query! {
 }
 
 query! {
-    // FIXME: this could also be generic over a type bounded by some trait
-    // SingleSelection, on which the delete() method would be defined. But this means
-    // users need to import that trait. Come back to this.
-    enum Delete -> Writes {
-        DeleteGet { get: Get }
+    Update -> Writes {
+        selection: json::Json,
+        doc: json::Json
+    } ty::UPDATE
+}
+
+pub fn update(selection: json::Json, doc: json::Json) -> Update {
+    Update { selection: selection, doc: doc }
+}
+
+query! {
+    Delete -> Writes {
+        selection: json::Json
     } ty::DELETE
 }
 
+pub fn delete(selection: json::Json) -> Delete {
+    Delete { selection: selection }
+}
+
 query! {
-    IndexCreate -> () {
+    IndexCreate -> Created {
         table: Table,
-        name: String
-    } ty::INDEX_CREATE
+        name: String ; ..func
+    } opt_args { geo, multi } ty::INDEX_CREATE
+}
+
+query! {
+    IndexRename -> Renamed {
+        table: Table,
+        old_name: String,
+        new_name: String
+    } opt_args { overwrite } ty::INDEX_RENAME
 }
 
 query! {
-    IndexDrop -> () {
+    IndexDrop -> Dropped {
         table: Table,
         name: String
     } ty::INDEX_DROP
@@ -145,3 +274,62 @@ query! {
     } ty::INDEX_LIST
 }
 
+query! {
+    IndexStatusTerm -> Vec<IndexStatus> {
+        table: Table ; ..names
+    } ty::INDEX_STATUS
+}
+
+query! {
+    IndexWait -> Vec<IndexStatus> {
+        table: Table ; ..names
+    } ty::INDEX_WAIT
+}
+
+/// The status of a secondary index, from `index_status` or `index_wait`.
+/// While an index is being built, `blocks_processed` and `blocks_total`
+/// show how far along it is.
+#[deriving(Decodable, Show)]
+pub struct IndexStatus {
+    pub index: String,
+    pub ready: bool,
+    pub blocks_processed: Option<u64>,
+    pub blocks_total: Option<u64>,
+    /// The index function, in the server's internal binary format.
+    pub function: Option<Binary>
+}
+
+impl<'a> FromResponse<'a> for Vec<IndexStatus> {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Vec<IndexStatus>> {
+        from_response::decode(try!(from_response::atom(res)))
+    }
+}
+
+impl_expr! {
+    TableCreate, TableDrop, TableList, Table, Get, Insert, Update, Delete,
+    IndexCreate, IndexDrop, IndexList, IndexRename, IndexStatusTerm, IndexWait
+}
+
+impl_selection! { Table, Get }
+
+#[cfg(test)]
+mod test {
+    use from_response;
+    use serialize::json;
+
+    use super::IndexStatus;
+
+    #[test]
+    fn test_index_status_decode() {
+        let datum = json::from_str(r#"[{"index": "a", "ready": true},
+                                       {"index": "b", "ready": false,
+                                        "blocks_processed": 10, "blocks_total": 40}]"#).unwrap();
+        let statuses: Vec<IndexStatus> = from_response::decode(datum).unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].index.as_slice(), "a");
+        assert!(statuses[0].ready);
+        assert_eq!(statuses[0].blocks_total, None);
+        assert!(!statuses[1].ready);
+        assert_eq!((statuses[1].blocks_processed, statuses[1].blocks_total), (Some(10), Some(40)));
+    }
+}
@@ -1,11 +1,52 @@
 pub type TermType = i64;
 
+pub const MAKE_ARRAY: TermType = 2;
+pub const MAKE_OBJ: TermType = 3;
+
+pub const VAR: TermType = 10;
+pub const ERROR: TermType = 12;
+
 pub const DB: TermType = 14;
 pub const TABLE: TermType = 15;
 pub const GET: TermType = 16;
 
+pub const EQ: TermType = 17;
+pub const NE: TermType = 18;
+pub const LT: TermType = 19;
+pub const LE: TermType = 20;
+pub const GT: TermType = 21;
+pub const GE: TermType = 22;
+pub const NOT: TermType = 23;
+
 pub const ADD: TermType = 24;
+pub const SUB: TermType = 25;
+pub const MUL: TermType = 26;
+pub const DIV: TermType = 27;
+pub const MOD: TermType = 28;
+pub const APPEND: TermType = 29;
+pub const SLICE: TermType = 30;
+
+pub const GET_FIELD: TermType = 31;
+pub const HAS_FIELDS: TermType = 32;
+pub const PLUCK: TermType = 33;
+pub const WITHOUT: TermType = 34;
+pub const MERGE: TermType = 35;
+pub const REDUCE: TermType = 37;
+pub const MAP: TermType = 38;
+pub const FILTER: TermType = 39;
+pub const CONCAT_MAP: TermType = 40;
+
+pub const ORDER_BY: TermType = 41;
+pub const DISTINCT: TermType = 42;
+pub const COUNT: TermType = 43;
+pub const NTH: TermType = 45;
+pub const INNER_JOIN: TermType = 48;
+pub const OUTER_JOIN: TermType = 49;
+pub const EQ_JOIN: TermType = 50;
+pub const COERCE_TO: TermType = 51;
+pub const TYPE_OF: TermType = 52;
 
+pub const UPDATE: TermType = 53;
 pub const DELETE: TermType = 54;
 pub const INSERT: TermType = 56;
 pub const DB_CREATE: TermType = 57;
@@ -14,6 +55,221 @@ pub const DB_LIST: TermType = 59;
 pub const TABLE_CREATE: TermType = 60;
 pub const TABLE_DROP: TermType = 61;
 pub const TABLE_LIST: TermType = 62;
+pub const BRANCH: TermType = 65;
+pub const ANY: TermType = 66; // or
+pub const ALL: TermType = 67; // and
+pub const FOR_EACH: TermType = 68;
+
+pub const FUNC: TermType = 69;
+pub const SKIP: TermType = 70;
+pub const LIMIT: TermType = 71;
+pub const ZIP: TermType = 72;
+pub const ASC: TermType = 73;
+pub const DESC: TermType = 74;
 pub const INDEX_CREATE: TermType = 75;
 pub const INDEX_DROP: TermType = 76;
 pub const INDEX_LIST: TermType = 77;
+pub const INFO: TermType = 79;
+pub const PREPEND: TermType = 80;
+pub const SAMPLE: TermType = 81;
+pub const INSERT_AT: TermType = 82;
+pub const DELETE_AT: TermType = 83;
+pub const CHANGE_AT: TermType = 84;
+pub const SPLICE_AT: TermType = 85;
+pub const IS_EMPTY: TermType = 86;
+pub const INDEXES_OF: TermType = 87;
+pub const SET_INSERT: TermType = 88;
+pub const SET_INTERSECTION: TermType = 89;
+pub const SET_UNION: TermType = 90;
+pub const SET_DIFFERENCE: TermType = 91;
+pub const DEFAULT: TermType = 92;
+pub const CONTAINS: TermType = 93;
+
+pub const KEYS: TermType = 94;
+pub const DIFFERENCE: TermType = 95;
+pub const WITH_FIELDS: TermType = 96;
+pub const MATCH: TermType = 97;
+pub const ISO8601: TermType = 99;
+pub const TO_ISO8601: TermType = 100;
+pub const EPOCH_TIME: TermType = 101;
+pub const TO_EPOCH_TIME: TermType = 102;
+pub const NOW: TermType = 103;
+pub const IN_TIMEZONE: TermType = 104;
+pub const DURING: TermType = 105;
+pub const DATE: TermType = 106;
+pub const TIME_OF_DAY: TermType = 126;
+pub const TIMEZONE: TermType = 127;
+pub const YEAR: TermType = 128;
+pub const MONTH: TermType = 129;
+pub const DAY: TermType = 130;
+pub const DAY_OF_WEEK: TermType = 131;
+pub const DAY_OF_YEAR: TermType = 132;
+pub const HOURS: TermType = 133;
+pub const MINUTES: TermType = 134;
+pub const SECONDS: TermType = 135;
+pub const TIME: TermType = 136;
+pub const LITERAL: TermType = 137;
+pub const INDEX_STATUS: TermType = 139;
+pub const INDEX_WAIT: TermType = 140;
+pub const UPCASE: TermType = 141;
+pub const DOWNCASE: TermType = 142;
+pub const GROUP: TermType = 144;
+pub const SUM: TermType = 145;
+pub const AVG: TermType = 146;
+pub const MIN: TermType = 147;
+pub const MAX: TermType = 148;
+pub const SPLIT: TermType = 149;
+pub const UNGROUP: TermType = 150;
+pub const CHANGES: TermType = 152;
+pub const BINARY: TermType = 155;
+pub const INDEX_RENAME: TermType = 156;
+pub const GEOJSON: TermType = 157;
+pub const TO_GEOJSON: TermType = 158;
+pub const POINT: TermType = 159;
+pub const LINE: TermType = 160;
+pub const POLYGON: TermType = 161;
+pub const DISTANCE: TermType = 162;
+pub const INTERSECTS: TermType = 163;
+pub const INCLUDES: TermType = 164;
+pub const CIRCLE: TermType = 165;
+pub const GET_INTERSECTING: TermType = 166;
+pub const FILL: TermType = 167;
+pub const GET_NEAREST: TermType = 168;
+
+pub const BRACKET: TermType = 170;
+pub const POLYGON_SUB: TermType = 171;
+
+/// The name of a term type, as its ReQL function is named, e.g. `"insert"`.
+pub fn name(term_type: TermType) -> Option<&'static str> {
+    match term_type {
+        MAKE_ARRAY => Some("make_array"),
+        MAKE_OBJ => Some("make_obj"),
+        VAR => Some("var"),
+        ERROR => Some("error"),
+        DB => Some("db"),
+        TABLE => Some("table"),
+        GET => Some("get"),
+        EQ => Some("eq"),
+        NE => Some("ne"),
+        LT => Some("lt"),
+        LE => Some("le"),
+        GT => Some("gt"),
+        GE => Some("ge"),
+        NOT => Some("not"),
+        ADD => Some("add"),
+        SUB => Some("sub"),
+        MUL => Some("mul"),
+        DIV => Some("div"),
+        MOD => Some("mod"),
+        APPEND => Some("append"),
+        SLICE => Some("slice"),
+        GET_FIELD => Some("get_field"),
+        HAS_FIELDS => Some("has_fields"),
+        PLUCK => Some("pluck"),
+        WITHOUT => Some("without"),
+        MERGE => Some("merge"),
+        REDUCE => Some("reduce"),
+        MAP => Some("map"),
+        FILTER => Some("filter"),
+        CONCAT_MAP => Some("concat_map"),
+        ORDER_BY => Some("order_by"),
+        DISTINCT => Some("distinct"),
+        COUNT => Some("count"),
+        NTH => Some("nth"),
+        INNER_JOIN => Some("inner_join"),
+        OUTER_JOIN => Some("outer_join"),
+        EQ_JOIN => Some("eq_join"),
+        COERCE_TO => Some("coerce_to"),
+        TYPE_OF => Some("type_of"),
+        UPDATE => Some("update"),
+        DELETE => Some("delete"),
+        INSERT => Some("insert"),
+        DB_CREATE => Some("db_create"),
+        DB_DROP => Some("db_drop"),
+        DB_LIST => Some("db_list"),
+        TABLE_CREATE => Some("table_create"),
+        TABLE_DROP => Some("table_drop"),
+        TABLE_LIST => Some("table_list"),
+        BRANCH => Some("branch"),
+        ANY => Some("or"),
+        ALL => Some("and"),
+        FOR_EACH => Some("for_each"),
+        FUNC => Some("func"),
+        SKIP => Some("skip"),
+        LIMIT => Some("limit"),
+        ZIP => Some("zip"),
+        ASC => Some("asc"),
+        DESC => Some("desc"),
+        INDEX_CREATE => Some("index_create"),
+        INDEX_DROP => Some("index_drop"),
+        INDEX_LIST => Some("index_list"),
+        INFO => Some("info"),
+        PREPEND => Some("prepend"),
+        SAMPLE => Some("sample"),
+        INSERT_AT => Some("insert_at"),
+        DELETE_AT => Some("delete_at"),
+        CHANGE_AT => Some("change_at"),
+        SPLICE_AT => Some("splice_at"),
+        IS_EMPTY => Some("is_empty"),
+        INDEXES_OF => Some("indexes_of"),
+        SET_INSERT => Some("set_insert"),
+        SET_INTERSECTION => Some("set_intersection"),
+        SET_UNION => Some("set_union"),
+        SET_DIFFERENCE => Some("set_difference"),
+        DEFAULT => Some("default"),
+        CONTAINS => Some("contains"),
+        KEYS => Some("keys"),
+        DIFFERENCE => Some("difference"),
+        WITH_FIELDS => Some("with_fields"),
+        MATCH => Some("match"),
+        ISO8601 => Some("iso8601"),
+        TO_ISO8601 => Some("to_iso8601"),
+        EPOCH_TIME => Some("epoch_time"),
+        TO_EPOCH_TIME => Some("to_epoch_time"),
+        NOW => Some("now"),
+        IN_TIMEZONE => Some("in_timezone"),
+        DURING => Some("during"),
+        DATE => Some("date"),
+        TIME_OF_DAY => Some("time_of_day"),
+        TIMEZONE => Some("timezone"),
+        YEAR => Some("year"),
+        MONTH => Some("month"),
+        DAY => Some("day"),
+        DAY_OF_WEEK => Some("day_of_week"),
+        DAY_OF_YEAR => Some("day_of_year"),
+        HOURS => Some("hours"),
+        MINUTES => Some("minutes"),
+        SECONDS => Some("seconds"),
+        TIME => Some("time"),
+        LITERAL => Some("literal"),
+        INDEX_STATUS => Some("index_status"),
+        INDEX_WAIT => Some("index_wait"),
+        UPCASE => Some("upcase"),
+        DOWNCASE => Some("downcase"),
+        GROUP => Some("group"),
+        SUM => Some("sum"),
+        AVG => Some("avg"),
+        MIN => Some("min"),
+        MAX => Some("max"),
+        SPLIT => Some("split"),
+        UNGROUP => Some("ungroup"),
+        CHANGES => Some("changes"),
+        BINARY => Some("binary"),
+        INDEX_RENAME => Some("index_rename"),
+        GEOJSON => Some("geojson"),
+        TO_GEOJSON => Some("to_geojson"),
+        POINT => Some("point"),
+        LINE => Some("line"),
+        POLYGON => Some("polygon"),
+        DISTANCE => Some("distance"),
+        INTERSECTS => Some("intersects"),
+        INCLUDES => Some("includes"),
+        CIRCLE => Some("circle"),
+        GET_INTERSECTING => Some("get_intersecting"),
+        FILL => Some("fill"),
+        GET_NEAREST => Some("get_nearest"),
+        BRACKET => Some("bracket"),
+        POLYGON_SUB => Some("polygon_sub"),
+        _ => None
+    }
+}
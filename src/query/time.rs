@@ -0,0 +1,251 @@
+use std::collections::TreeMap;
+
+use serialize::{Decodable, Decoder};
+use serialize::json::{mod, Json, ToJson};
+
+use errors::RdbResult;
+use from_response::{mod, FromResponse};
+use net::{Connection, Response};
+
+use super::ToTerm;
+use super::term_type as ty;
+
+/// A point in time, as RethinkDB's `TIME` pseudo-type: seconds since the Unix
+/// epoch, along with the timezone it's expressed in, e.g. `"+00:00"`. Times
+/// are decoded from the pseudo-type wherever a `Time` is expected; within
+/// `Json` results they're left as pseudo-type objects.
+#[deriving(Clone, Show, PartialEq)]
+pub struct Time {
+    pub epoch_time: f64,
+    pub timezone: String
+}
+
+impl Time {
+    /// A time in UTC.
+    pub fn from_epoch_time(epoch_time: f64) -> Time {
+        Time { epoch_time: epoch_time, timezone: "+00:00".to_string() }
+    }
+}
+
+impl ToJson for Time {
+    fn to_json(&self) -> Json {
+        let mut d = TreeMap::new();
+        d.insert("$reql_type$".to_string(), "TIME".to_string().to_json());
+        d.insert("epoch_time".to_string(), self.epoch_time.to_json());
+        d.insert("timezone".to_string(), self.timezone.to_json());
+        json::Object(d)
+    }
+}
+
+impl ToTerm for Time {
+    fn to_term(&self) -> Json {
+        self.to_json()
+    }
+}
+
+impl<E, D: Decoder<E>> Decodable<D, E> for Time {
+    fn decode(d: &mut D) -> Result<Time, E> {
+        d.read_struct("Time", 3, |d| {
+            let reql_type: String = try!(d.read_struct_field("$reql_type$", 0, |d| Decodable::decode(d)));
+            if reql_type.as_slice() != "TIME" {
+                return Err(d.error(format!("expected a TIME, got {}", reql_type).as_slice()));
+            }
+            Ok(Time {
+                epoch_time: try!(d.read_struct_field("epoch_time", 1, |d| Decodable::decode(d))),
+                timezone: try!(d.read_struct_field("timezone", 2, |d| Decodable::decode(d)))
+            })
+        })
+    }
+}
+
+impl<'a> FromResponse<'a> for Time {
+    fn from_response(res: Response, _: &'a mut Connection) -> RdbResult<Time> {
+        from_response::decode(try!(from_response::atom(res)))
+    }
+}
+
+/// The `time_format` run option; see `Connection::time_format`. Times arrive
+/// as the `TIME` pseudo-type in both formats, so they decode as `Time` either
+/// way; with `Raw`, `Time::to_json` gives back the pseudo-type object as it
+/// arrived, and `Json` results keep it as is.
+#[deriving(Clone, Show, PartialEq)]
+pub enum TimeFormat {
+    Native,
+    Raw
+}
+
+impl ToJson for TimeFormat {
+    fn to_json(&self) -> Json {
+        match *self {
+            TimeFormat::Native => "native".to_string().to_json(),
+            TimeFormat::Raw => "raw".to_string().to_json()
+        }
+    }
+}
+
+query! {
+    Now -> Time ; ty::NOW
+}
+
+/// The time at which the query started running on the server.
+pub fn now() -> Now {
+    Now
+}
+
+query! {
+    TimeTerm -> Time {
+        ; ..args
+    } ty::TIME
+}
+
+/// Midnight of the given date in `timezone`, e.g. `"-07:00"` or `"Z"`.
+pub fn time(year: int, month: uint, day: uint, timezone: &str) -> TimeTerm {
+    TimeTerm {
+        args: vec![year.to_json(), month.to_json(), day.to_json(), timezone.to_term()]
+    }
+}
+
+pub fn time_hms(year: int, month: uint, day: uint, hour: uint, minute: uint, second: f64,
+                timezone: &str) -> TimeTerm {
+    TimeTerm {
+        args: vec![year.to_json(), month.to_json(), day.to_json(), hour.to_json(),
+                   minute.to_json(), second.to_json(), timezone.to_term()]
+    }
+}
+
+query! {
+    EpochTime -> Time {
+        epoch_time: f64
+    } ty::EPOCH_TIME
+}
+
+pub fn epoch_time(epoch_time: f64) -> EpochTime {
+    EpochTime { epoch_time: epoch_time }
+}
+
+query! {
+    Iso8601 -> Time {
+        string: String
+    } opt_args { default_timezone } ty::ISO8601
+}
+
+/// Parses an ISO 8601 date and time. Use `Iso8601::default_timezone` for
+/// strings which don't give a timezone.
+pub fn iso8601(string: &str) -> Iso8601 {
+    Iso8601 { string: string.into_string(), opt_args: TreeMap::new() }
+}
+
+query! {
+    ToIso8601 -> String {
+        time: Json
+    } ty::TO_ISO8601
+}
+
+pub fn to_iso8601(time: Json) -> ToIso8601 {
+    ToIso8601 { time: time }
+}
+
+query! {
+    ToEpochTime -> f64 {
+        time: Json
+    } ty::TO_EPOCH_TIME
+}
+
+pub fn to_epoch_time(time: Json) -> ToEpochTime {
+    ToEpochTime { time: time }
+}
+
+query! {
+    InTimezone -> Time {
+        time: Json,
+        timezone: String
+    } ty::IN_TIMEZONE
+}
+
+pub fn in_timezone(time: Json, timezone: &str) -> InTimezone {
+    InTimezone { time: time, timezone: timezone.into_string() }
+}
+
+query! {
+    During -> bool {
+        time: Json,
+        start: Json,
+        end: Json
+    } opt_args { left_bound, right_bound } ty::DURING
+}
+
+pub fn during(time: Json, start: Json, end: Json) -> During {
+    During { time: time, start: start, end: end, opt_args: TreeMap::new() }
+}
+
+query! {
+    Date -> Time {
+        time: Json
+    } ty::DATE
+}
+
+pub fn date(time: Json) -> Date {
+    Date { time: time }
+}
+
+query! {
+    Timezone -> String {
+        time: Json
+    } ty::TIMEZONE
+}
+
+pub fn timezone(time: Json) -> Timezone {
+    Timezone { time: time }
+}
+
+// The parts of a time, e.g. r.now().year(), r.now().seconds()
+macro_rules! time_part {
+    ($($name:ident $ctor:ident -> $resp:ty $term_ty:expr),+) => {$(
+        query! {
+            $name -> $resp {
+                time: Json
+            } $term_ty
+        }
+
+        pub fn $ctor(time: Json) -> $name {
+            $name { time: time }
+        }
+    )+}
+}
+
+time_part! {
+    TimeOfDay time_of_day -> f64 ty::TIME_OF_DAY,
+    Year year -> i64 ty::YEAR,
+    Month month -> u64 ty::MONTH,
+    Day day -> u64 ty::DAY,
+    DayOfWeek day_of_week -> u64 ty::DAY_OF_WEEK,
+    DayOfYear day_of_year -> u64 ty::DAY_OF_YEAR,
+    Hours hours -> u64 ty::HOURS,
+    Minutes minutes -> u64 ty::MINUTES,
+    Seconds seconds -> f64 ty::SECONDS
+}
+
+impl_expr! {
+    Now, TimeTerm, EpochTime, Iso8601, ToIso8601, ToEpochTime, InTimezone, During, Date,
+    Timezone, TimeOfDay, Year, Month, Day, DayOfWeek, DayOfYear, Hours, Minutes, Seconds
+}
+
+#[cfg(test)]
+mod test {
+    use from_response;
+    use serialize::json::{mod, ToJson};
+
+    use super::Time;
+
+    #[test]
+    fn test_time_round_trip() {
+        let datum = json::from_str(r#"{"$reql_type$": "TIME", "epoch_time": 1416009600.5,
+                                       "timezone": "-08:00"}"#).unwrap();
+        let time: Time = from_response::decode(datum.clone()).unwrap();
+        assert_eq!(time, Time { epoch_time: 1416009600.5, timezone: "-08:00".to_string() });
+        assert_eq!(time.to_json(), datum);
+
+        let datum = json::from_str(r#"{"$reql_type$": "GROUPED_DATA", "data": []}"#).unwrap();
+        assert!(from_response::decode::<Time>(datum).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! Declaring the databases, tables and indexes an application needs, and
+//! creating whichever of them don't exist yet.
+//!
+//! ```
+//! let schema = Schema::new()
+//!     .db(DbSchema::new("app")
+//!         .table(TableSchema::new("users")
+//!             .primary_key("email")
+//!             .index(IndexSchema::new("name"))
+//!             .index(IndexSchema::compound("full_name", &["last", "first"]))
+//!             .index(IndexSchema::new("tags").multi())));
+//! let provisioned = try!(schema.ensure(&mut conn));
+//! ```
+
+use errors::{Error, RdbResult};
+use net::Connection;
+use query as r;
+use query::Query;
+
+#[deriving(Clone, Show)]
+pub struct Schema {
+    dbs: Vec<DbSchema>
+}
+
+#[deriving(Clone, Show)]
+pub struct DbSchema {
+    name: String,
+    tables: Vec<TableSchema>
+}
+
+#[deriving(Clone, Show)]
+pub struct TableSchema {
+    name: String,
+    primary_key: Option<String>,
+    indexes: Vec<IndexSchema>
+}
+
+/// A secondary index, either on the field of the same name or on several
+/// fields at once.
+#[deriving(Clone, Show)]
+pub struct IndexSchema {
+    name: String,
+    fields: Option<Vec<String>>,
+    multi: bool,
+    geo: bool
+}
+
+/// What `Schema::ensure` had to create. Tables are named as `db.table`, and
+/// indexes as `db.table.index`.
+#[deriving(Clone, Show, PartialEq)]
+pub struct Provisioned {
+    pub dbs: Vec<String>,
+    pub tables: Vec<String>,
+    pub indexes: Vec<String>
+}
+
+impl Schema {
+    pub fn new() -> Schema {
+        Schema { dbs: vec![] }
+    }
+
+    pub fn db(mut self, db: DbSchema) -> Schema {
+        self.dbs.push(db);
+        self
+    }
+
+    /// Creates the databases, tables and indexes which don't exist yet, then
+    /// waits for every declared index to be ready. Nothing is ever dropped or
+    /// changed, so it's safe to run each time an application starts, even
+    /// from several processes at once.
+    pub fn ensure(&self, conn: &mut Connection) -> RdbResult<Provisioned> {
+        let mut provisioned = Provisioned { dbs: vec![], tables: vec![], indexes: vec![] };
+
+        let existing = try!(r::db_list().run(conn));
+        for db in self.dbs.iter() {
+            if let Some(create) = db.create(existing.as_slice()) {
+                if try!(created(create.run(conn))) {
+                    provisioned.dbs.push(db.name.clone());
+                }
+            }
+            try!(db.ensure(conn, &mut provisioned));
+        }
+        Ok(provisioned)
+    }
+}
+
+impl DbSchema {
+    pub fn new(name: &str) -> DbSchema {
+        DbSchema { name: name.into_string(), tables: vec![] }
+    }
+
+    pub fn table(mut self, table: TableSchema) -> DbSchema {
+        self.tables.push(table);
+        self
+    }
+
+    // The query creating this database, unless it's one of `existing`
+    fn create(&self, existing: &[String]) -> Option<r::DbCreate> {
+        if existing.contains(&self.name) {
+            None
+        } else {
+            Some(r::db_create(self.name.as_slice()))
+        }
+    }
+
+    fn ensure(&self, conn: &mut Connection, provisioned: &mut Provisioned) -> RdbResult<()> {
+        let existing = try!(r::db(self.name.as_slice()).table_list().run(conn));
+        for table in self.tables.iter() {
+            if let Some(create) = table.create(self.name.as_slice(), existing.as_slice()) {
+                if try!(created(create.run(conn))) {
+                    provisioned.tables.push(format!("{}.{}", self.name, table.name));
+                }
+            }
+            try!(table.ensure(conn, self.name.as_slice(), provisioned));
+        }
+        Ok(())
+    }
+}
+
+impl TableSchema {
+    pub fn new(name: &str) -> TableSchema {
+        TableSchema { name: name.into_string(), primary_key: None, indexes: vec![] }
+    }
+
+    /// The primary key to create the table with, if not `id`. This isn't
+    /// checked for tables which already exist.
+    pub fn primary_key(mut self, key: &str) -> TableSchema {
+        self.primary_key = Some(key.into_string());
+        self
+    }
+
+    pub fn index(mut self, index: IndexSchema) -> TableSchema {
+        self.indexes.push(index);
+        self
+    }
+
+    // The query creating this table in `db`, unless it's one of `existing`
+    fn create(&self, db: &str, existing: &[String]) -> Option<r::TableCreate> {
+        if existing.contains(&self.name) {
+            return None;
+        }
+        let create = r::db(db).table_create(self.name.as_slice());
+        Some(match self.primary_key {
+            Some(ref key) => create.primary_key(key.as_slice()),
+            None => create
+        })
+    }
+
+    // The query waiting for every declared index of this table, new or not
+    fn wait(&self, db: &str) -> Option<r::IndexWait> {
+        if self.indexes.is_empty() {
+            return None;
+        }
+        let names: Vec<&str> = self.indexes.iter().map(|x| x.name.as_slice()).collect();
+        Some(r::db(db).table(self.name.as_slice()).index_wait(names.as_slice()))
+    }
+
+    fn ensure(&self, conn: &mut Connection, db: &str, provisioned: &mut Provisioned) -> RdbResult<()> {
+        let wait = match self.wait(db) {
+            Some(wait) => wait,
+            None => return Ok(())
+        };
+
+        let table = || r::db(db).table(self.name.as_slice());
+        let existing = try!(table().index_list().run(conn));
+        for index in self.indexes.iter() {
+            if let Some(create) = index.create(table(), existing.as_slice()) {
+                if try!(created(create.run(conn))) {
+                    provisioned.indexes.push(format!("{}.{}.{}", db, self.name, index.name));
+                }
+            }
+        }
+
+        try!(wait.run(conn));
+        Ok(())
+    }
+}
+
+impl IndexSchema {
+    /// An index on the field `name`.
+    pub fn new(name: &str) -> IndexSchema {
+        IndexSchema { name: name.into_string(), fields: None, multi: false, geo: false }
+    }
+
+    /// An index on the array of the values of `fields`.
+    pub fn compound(name: &str, fields: &[&str]) -> IndexSchema {
+        IndexSchema {
+            name: name.into_string(),
+            fields: Some(fields.iter().map(|x| x.into_string()).collect()),
+            multi: false,
+            geo: false
+        }
+    }
+
+    /// Indexes each element of an array field separately.
+    pub fn multi(mut self) -> IndexSchema {
+        self.multi = true;
+        self
+    }
+
+    /// Indexes a geometry field, for `get_intersecting` and `get_nearest`.
+    pub fn geo(mut self) -> IndexSchema {
+        self.geo = true;
+        self
+    }
+
+    // The query creating this index on `table`, unless it's one of `existing`
+    fn create(&self, table: r::Table, existing: &[String]) -> Option<r::IndexCreate> {
+        if existing.contains(&self.name) {
+            return None;
+        }
+        let mut create = match self.fields {
+            Some(ref fields) => {
+                let fields: Vec<&str> = fields.iter().map(|x| x.as_slice()).collect();
+                table.index_create_compound(self.name.as_slice(), fields.as_slice())
+            }
+            None => table.index_create(self.name.as_slice())
+        };
+        if self.multi {
+            create = create.multi(true);
+        }
+        if self.geo {
+            create = create.geo(true);
+        }
+        Some(create)
+    }
+}
+
+// Another process may have created the same thing between our listing and
+// creating it, which is fine: all that matters is that it exists. The server
+// reports this as a plain runtime error, so the only way to tell it apart is
+// the "already exists" in its message, e.g. "Table `app.users` already
+// exists."; if a server version words it differently, `ensure` will fail on
+// the race instead.
+fn created(result: RdbResult<r::Created>) -> RdbResult<bool> {
+    match result {
+        Ok(created) => Ok(created.created > 0),
+        Err(Error::RuntimeError(ref msg)) if msg.as_slice().contains("already exists") => Ok(false),
+        Err(e) => Err(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use errors::Error;
+    use query as r;
+    use serialize::json::ToJson;
+
+    use super::{DbSchema, TableSchema, IndexSchema, created};
+
+    #[test]
+    fn test_created() {
+        let ok = r::Created { created: 1, config_changes: vec![] };
+        assert_eq!(created(Ok(ok)).unwrap(), true);
+
+        let exists = Error::RuntimeError("Table `app.users` already exists.".to_string());
+        assert_eq!(created(Err(exists)).unwrap(), false);
+
+        let other = Error::RuntimeError("Database `app` does not exist.".to_string());
+        assert!(created(Err(other)).is_err());
+    }
+
+    #[test]
+    fn test_create_db() {
+        let db = DbSchema::new("app");
+        assert!(db.create(&["app".to_string()]).is_none());
+        assert_eq!(db.create(&["other".to_string()]).unwrap().to_json(), json!([57, ["app"]]));
+    }
+
+    #[test]
+    fn test_create_table() {
+        let table = TableSchema::new("users").primary_key("email");
+        assert!(table.create("app", &["users".to_string()]).is_none());
+        assert_eq!(table.create("app", &[]).unwrap().to_json(),
+                   json!([60, [[14, ["app"]], "users"], {"primary_key": "email"}]));
+    }
+
+    #[test]
+    fn test_create_index() {
+        let users = || r::db("app").table("users");
+        let existing = &["name".to_string()];
+
+        assert!(IndexSchema::new("name").create(users(), existing).is_none());
+        assert_eq!(IndexSchema::new("tags").multi().create(users(), existing).unwrap().to_json(),
+                   json!([75, [[15, [[14, ["app"]], "users"]], "tags"], {"multi": true}]));
+        assert_eq!(IndexSchema::new("location").geo().create(users(), existing).unwrap().to_json(),
+                   json!([75, [[15, [[14, ["app"]], "users"]], "location"], {"geo": true}]));
+    }
+
+    #[test]
+    fn test_wait() {
+        assert!(TableSchema::new("users").wait("app").is_none());
+
+        // Existing indexes are waited for too, as another process may have
+        // only just created them
+        let table = TableSchema::new("users")
+            .index(IndexSchema::new("name"))
+            .index(IndexSchema::new("tags").multi());
+        assert_eq!(table.wait("app").unwrap().to_json(),
+                   json!([140, [[15, [[14, ["app"]], "users"]], "name", "tags"]]));
+    }
+}